@@ -0,0 +1,246 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018, 2022 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The user configuration file.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use diceware::{EmbeddedList, Encoding, Normalization};
+
+use crate::{output::Format, xdg};
+
+/// The user configuration, providing defaults for the command-line options.
+///
+/// It is read from `$XDG_CONFIG_HOME/diceware/config.toml`, and every field
+/// is optional:
+///
+/// ```toml
+/// word_list = "fr"
+/// words = 8
+/// with_special_char = true
+/// checksum_word = false
+/// separator = "-"
+/// format = "json"
+///
+/// [load_options]
+/// normalization = "nfkc"
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The path of the configuration file, if it has been read.
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+    /// The default word list.
+    pub word_list: Option<ListSetting>,
+    /// The default number of words.
+    pub words: Option<usize>,
+    /// Whether to add a special character by default.
+    pub with_special_char: Option<bool>,
+    /// Whether to append a checksum word by default.
+    pub checksum_word: Option<bool>,
+    /// The default separator between words.
+    pub separator: Option<String>,
+    /// The default output format.
+    pub format: Option<Format>,
+    /// The default word list load options.
+    pub load_options: LoadOptions,
+}
+
+/// A word list in the configuration.
+///
+/// It can be given by name, like `"fr"` or the name of a list from the
+/// registry, or as a source, like `{ file = "words.txt" }` or
+/// `{ embedded = "fr" }`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+pub enum ListSetting {
+    /// A word list name.
+    Name(String),
+    /// A word list file.
+    File {
+        /// The path of the file, `-` being the standard input.
+        file: String,
+    },
+    /// An embedded word list.
+    Embedded {
+        /// The embedded list.
+        embedded: EmbeddedList,
+    },
+}
+
+/// The default word list load options.
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoadOptions {
+    /// The Unicode normalization form of the words.
+    pub normalization: Option<Normalization>,
+    /// Whether to transliterate the words to ASCII.
+    pub ascii_folding: Option<bool>,
+    /// Whether to read list files strictly.
+    pub strict: Option<bool>,
+    /// The encoding of list files.
+    pub encoding: Option<Encoding>,
+}
+
+impl Config {
+    /// Loads the user configuration.
+    ///
+    /// A missing configuration file is not an error: the default
+    /// configuration is returned instead.
+    pub fn load() -> Result<Self, String> {
+        match path() {
+            Some(path) => Self::from_file(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Loads the configuration from a file.
+    fn from_file(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                let config: Self = toml::from_str(&content).map_err(|err| {
+                    format!("{}: {}", path.display(), err.message())
+                })?;
+
+                Ok(Self {
+                    path: Some(path.to_owned()),
+                    ..config
+                })
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                Ok(Self::default())
+            }
+            Err(err) => Err(format!("{}: {err}", path.display())),
+        }
+    }
+}
+
+/// Returns the path of the configuration file.
+fn path() -> Option<PathBuf> {
+    Some(xdg::config_home()?.join("diceware").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn parses_an_empty_config() {
+        assert_eq!(toml::from_str::<Config>("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn parses_a_full_config() {
+        let config: Config = toml::from_str(
+            r#"
+                word_list = { embedded = "fr" }
+                words = 8
+                with_special_char = true
+                checksum_word = false
+                separator = "-"
+                format = "yaml"
+
+                [load_options]
+                normalization = "nfkc"
+                encoding = "auto"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                path: None,
+                word_list: Some(ListSetting::Embedded {
+                    embedded: EmbeddedList::FR
+                }),
+                words: Some(8),
+                with_special_char: Some(true),
+                checksum_word: Some(false),
+                separator: Some(String::from("-")),
+                format: Some(Format::Yaml),
+                load_options: LoadOptions {
+                    normalization: Some(Normalization::NFKC),
+                    ascii_folding: None,
+                    strict: None,
+                    encoding: Some(Encoding::Auto),
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn parses_a_word_list_file() {
+        let config: Config =
+            toml::from_str(r#"word_list = { file = "words.txt" }"#).unwrap();
+
+        assert_eq!(
+            config.word_list,
+            Some(ListSetting::File {
+                file: String::from("words.txt")
+            })
+        );
+    }
+
+    #[test]
+    fn refuses_several_word_list_sources() {
+        assert!(toml::from_str::<Config>(
+            r#"word_list = { file = "words.txt", embedded = "fr" }"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn parses_a_word_list_name() {
+        let config: Config =
+            toml::from_str(r#"word_list = "corp-en""#).unwrap();
+
+        assert_eq!(
+            config.word_list,
+            Some(ListSetting::Name(String::from("corp-en")))
+        );
+    }
+
+    #[test]
+    fn refuses_unknown_fields() {
+        assert!(toml::from_str::<Config>("special_char = true").is_err());
+    }
+
+    #[test]
+    fn ignores_a_missing_file() {
+        let path = Path::new("/nonexistent/diceware/config.toml");
+        assert_eq!(Config::from_file(path).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn reports_the_path_of_an_invalid_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("invalid-config.toml");
+        fs::write(&path, "words = \"eight\"").unwrap();
+        let result = Config::from_file(&path);
+
+        let message = result.unwrap_err();
+        assert!(message.starts_with(&path.display().to_string()));
+    }
+}
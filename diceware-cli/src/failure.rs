@@ -0,0 +1,248 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018, 2022 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! CLI errors and exit codes.
+
+use std::io;
+
+use serde::Serialize;
+
+use diceware::{Error, Severity, WordListError};
+
+use crate::{output::Format, print_diagnostic};
+
+/// A CLI error.
+#[derive(Debug)]
+pub struct Failure {
+    kind: Kind,
+    message: String,
+    error: Option<Error>,
+}
+
+/// The class of a CLI error, determining the exit code.
+///
+/// Exit codes follow the `sysexits.h` convention.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+    /// Invalid arguments (`EX_USAGE`).
+    Usage,
+    /// Invalid input data, like an invalid word list or an unknown word
+    /// (`EX_DATAERR`).
+    InvalidData,
+    /// A missing or unreadable input file (`EX_NOINPUT`).
+    NoInput,
+    /// An internal failure (`EX_SOFTWARE`).
+    Internal,
+    /// Another IO error (`EX_IOERR`).
+    Io,
+    /// An invalid configuration file (`EX_CONFIG`).
+    Config,
+}
+
+impl Kind {
+    /// Returns the exit code.
+    pub const fn exit_code(self) -> i32 {
+        match self {
+            Self::Usage => 64,
+            Self::InvalidData => 65,
+            Self::NoInput => 66,
+            Self::Internal => 70,
+            Self::Io => 74,
+            Self::Config => 78,
+        }
+    }
+}
+
+/// A CLI error, as printed in JSON.
+#[derive(Debug, Serialize)]
+struct JsonFailure<'a> {
+    kind: Kind,
+    exit_code: i32,
+    message: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<&'a Error>,
+}
+
+impl Failure {
+    /// Creates an error.
+    pub fn new(kind: Kind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            error: None,
+        }
+    }
+
+    /// Creates an invalid arguments error.
+    pub fn usage(message: impl Into<String>) -> Self {
+        Self::new(Kind::Usage, message)
+    }
+
+    /// Creates an invalid data error.
+    pub fn data(message: impl Into<String>) -> Self {
+        Self::new(Kind::InvalidData, message)
+    }
+
+    /// Creates an error from a library error.
+    ///
+    /// Word list diagnostics are reported one by line, in a compiler-like
+    /// format: `file:line: message`. For lists without a path, `source` is
+    /// used instead of the file.
+    pub fn from_error(error: Error, source: &str) -> Self {
+        let kind = match &error {
+            Error::IO { error, .. } => match error.kind() {
+                io::ErrorKind::NotFound | io::ErrorKind::PermissionDenied => {
+                    Kind::NoInput
+                }
+                _ => Kind::Io,
+            },
+            Error::WordList {
+                error: WordListError::Invalid(_),
+                ..
+            }
+            | Error::Lookup(_)
+            | Error::Mnemonic(_) => Kind::InvalidData,
+            Error::WordList {
+                error: WordListError::Ambiguous { .. },
+                ..
+            }
+            | Error::NoWords => Kind::Usage,
+            _ => Kind::Internal,
+        };
+
+        Self {
+            kind,
+            message: message(&error, source),
+            error: Some(error),
+        }
+    }
+
+    /// Returns the message.
+    #[cfg(test)]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the exit code.
+    pub const fn exit_code(&self) -> i32 {
+        self.kind.exit_code()
+    }
+
+    /// Prints the error on stderr.
+    ///
+    /// With the JSON format, it is printed as a JSON object on a single line,
+    /// containing the serialized library error, if any, as `details`.
+    pub fn print(&self, format: Format) {
+        let json = JsonFailure {
+            kind: self.kind,
+            exit_code: self.exit_code(),
+            message: &self.message,
+            details: self.error.as_ref(),
+        };
+
+        match (format, serde_json::to_string(&json)) {
+            (Format::Json, Ok(json)) => eprintln!("{json}"),
+            _ => {
+                for line in self.message.lines() {
+                    print_diagnostic(Severity::Error, line);
+                }
+            }
+        }
+    }
+}
+
+/// Builds the message of a library error.
+fn message(error: &Error, source: &str) -> String {
+    match error {
+        Error::WordList {
+            path,
+            error: WordListError::Invalid(diagnostics),
+        } => {
+            let source = path.as_deref().unwrap_or(source);
+
+            diagnostics
+                .iter()
+                .map(|diagnostic| match diagnostic.line {
+                    Some(line) => {
+                        format!("{source}:{line}: {}", diagnostic.kind)
+                    }
+                    None => format!("{source}: {}", diagnostic.kind),
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+
+        _ => error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use diceware::{EmbeddedList, WordList};
+
+    #[test]
+    fn tells_a_missing_file_from_an_invalid_list() {
+        let missing =
+            WordList::from_file("/nonexistent/words.txt").unwrap_err();
+        assert_eq!(Failure::from_error(missing, "").exit_code(), 66);
+
+        let words = vec![String::from("a"); 2];
+        let invalid = WordList::new(words).unwrap_err();
+        assert_eq!(Failure::from_error(invalid, "").exit_code(), 65);
+    }
+
+    #[test]
+    fn classifies_no_words_as_a_usage_error() {
+        let config =
+            diceware::Config::with_embedded(EmbeddedList::EN, 0, false);
+        let error = diceware::make_passphrase(config).unwrap_err();
+        assert_eq!(Failure::from_error(error, "").exit_code(), 64);
+    }
+
+    #[test]
+    fn reports_diagnostics_with_the_source() {
+        let words = vec![String::from("a"); 2];
+        let error = WordList::new(words).unwrap_err();
+        let failure = Failure::from_error(error, "<stdin>");
+
+        assert!(failure.message.starts_with("<stdin>:2: duplicate word"));
+    }
+
+    #[test]
+    fn serializes_to_json() {
+        let failure = Failure::from_error(Error::NoWords, "");
+        let json = JsonFailure {
+            kind: failure.kind,
+            exit_code: failure.exit_code(),
+            message: &failure.message,
+            details: failure.error.as_ref(),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&json).unwrap(),
+            serde_json::json!({
+                "kind": "usage",
+                "exit_code": 64,
+                "message": "No words to generate",
+                "details": "no_words",
+            })
+        );
+    }
+}
@@ -24,73 +24,945 @@
 #![deny(unused_must_use)]
 #![forbid(unsafe_code)]
 
-use std::process;
+mod config;
+mod failure;
+mod output;
+mod registry;
+mod settings;
+mod xdg;
 
-use clap::Parser;
+use std::{
+    collections::BTreeSet,
+    env, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+use clap::{
+    builder::BoolishValueParser, ArgMatches, Args, CommandFactory,
+    FromArgMatches, Parser, Subcommand, ValueEnum,
+};
+use failure::{Failure, Kind};
+use output::{Format, Generated};
 use owo_colors::{OwoColorize, Stream::Stderr, Style};
+use registry::Entry;
+use settings::{Origin, Resolver};
+
+use config::ListSetting;
+use diceware::{
+    mnemonic, Config, EmbeddedList, Encoding, LoadOptions, Normalization,
+    Severity, WordList,
+};
+
+/// The file name used to read a word list from the standard input.
+const STDIN: &str = "-";
 
-use diceware::{Config, EmbeddedList, Error};
+/// The name of a word list read from the standard input.
+const STDIN_NAME: &str = "<stdin>";
+
+/// The name of an embedded word list.
+const EMBEDDED_NAME: &str = "<embedded>";
 
 /// A Diceware passphrase generator.
 #[derive(Debug, Parser)]
 #[clap(name = "diceware", author, version)]
+#[clap(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
+    #[clap(subcommand)]
+    command: Option<Command>,
+    #[clap(flatten)]
+    generate: GenerateArgs,
+}
+
+/// Passphrase generation options.
+#[derive(Debug, Args)]
+struct GenerateArgs {
     /// The number of words to generate.
-    words: usize,
-    /// Use a diceware word file.
-    #[clap(long = "file", short = 'f', group = "word_list")]
+    #[clap(env = "DICEWARE_WORDS")]
+    words: Option<usize>,
+    #[clap(flatten)]
+    list: ListArgs,
+    /// Add a special character to the passphrase.
+    #[clap(long, short = 's', env = "DICEWARE_SPECIAL",
+        action,
+        value_parser = BoolishValueParser::new())]
+    with_special_char: bool,
+    /// Do not add a special character to the passphrase.
+    #[clap(long, env = "DICEWARE_NO_SPECIAL",
+        action,
+        value_parser = BoolishValueParser::new())]
+    no_special_char: bool,
+    /// Append a checksum word to the passphrase.
+    #[clap(long, short = 'c', env = "DICEWARE_CHECKSUM",
+        action,
+        value_parser = BoolishValueParser::new())]
+    checksum: bool,
+    /// Do not append a checksum word to the passphrase.
+    #[clap(long, env = "DICEWARE_NO_CHECKSUM",
+        action,
+        value_parser = BoolishValueParser::new())]
+    no_checksum: bool,
+    /// The separator between words [default: " "].
+    #[clap(long, env = "DICEWARE_SEPARATOR")]
+    separator: Option<String>,
+    /// The output format [default: text].
+    #[clap(long, value_enum, env = "DICEWARE_FORMAT")]
+    format: Option<Format>,
+    /// Show the effective settings and where they come from.
+    #[clap(long, action)]
+    show_config: bool,
+    /// Generate a deterministic passphrase from a seed (INSECURE, for tests).
+    #[clap(long, hide = true)]
+    insecure_seed: Option<u64>,
+}
+
+/// Word list selection.
+#[derive(Debug, Args)]
+struct ListArgs {
+    /// Use a diceware word file, or "-" to read it from the standard input.
+    #[clap(long = "file", short = 'f', env = "DICEWARE_FILE")]
     word_file: Option<String>,
     /// Use the English embedded word list.
-    #[clap(long = "en", group = "word_list")]
+    #[clap(long = "en", action)]
     english: bool,
     /// Use the French embedded word list.
-    #[clap(long = "fr", group = "word_list")]
+    #[clap(long = "fr", action)]
     french: bool,
-    /// Add a special character to the passphrase.
-    #[clap(long, short = 's')]
-    with_special_char: bool,
+    /// Use a word list by name, from the embedded lists or the registry.
+    #[clap(long, short = 'l', env = "DICEWARE_LIST")]
+    list: Option<String>,
+    /// The name of the selected word list, or its path.
+    #[clap(skip)]
+    name: String,
+    #[clap(flatten)]
+    load: LoadArgs,
 }
 
-fn main() {
-    let cli = Cli::parse();
+/// Word list load options.
+#[derive(Debug, Args)]
+struct LoadArgs {
+    /// The Unicode normalization form of the words (nfc or nfkc) [default:
+    /// nfc].
+    #[clap(long, env = "DICEWARE_NORMALIZATION")]
+    normalization: Option<Normalization>,
+    /// Transliterate the words to ASCII, like "Église" to "Eglise".
+    #[clap(long, env = "DICEWARE_ASCII",
+        action,
+        value_parser = BoolishValueParser::new())]
+    ascii: bool,
+    /// Read list files strictly, without comments or blank lines.
+    #[clap(long, env = "DICEWARE_STRICT",
+        action,
+        value_parser = BoolishValueParser::new())]
+    strict: bool,
+    /// The encoding of list files (utf-8, latin1, windows-1252 or auto)
+    /// [default: utf-8].
+    #[clap(long, env = "DICEWARE_ENCODING")]
+    encoding: Option<Encoding>,
+}
 
-    let config = if let Some(ref filename) = cli.word_file {
-        Config::with_filename(filename, cli.words, cli.with_special_char)
-    } else {
-        let list = if cli.english {
-            EmbeddedList::EN
-        } else if cli.french {
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Generate a passphrase (default).
+    Generate(GenerateArgs),
+    /// Encode hexadecimal data as words, followed by a checksum word.
+    Encode {
+        /// The data to encode, in hexadecimal.
+        data: String,
+        #[clap(flatten)]
+        list: ListArgs,
+    },
+    /// Verify the checksum word of a passphrase.
+    Verify {
+        /// The words of the passphrase, followed by the checksum word.
+        #[clap(required = true)]
+        words: Vec<String>,
+        #[clap(flatten)]
+        list: ListArgs,
+    },
+    /// Decode words, followed by a checksum word, to hexadecimal data.
+    Decode {
+        /// The words to decode.
+        #[clap(required = true)]
+        words: Vec<String>,
+        #[clap(flatten)]
+        list: ListArgs,
+    },
+    /// Manage word lists.
+    List {
+        #[clap(subcommand)]
+        command: ListCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ListCommand {
+    /// Check a word list file, exiting with an error if it is invalid.
+    Check {
+        /// The word list file, or "-" to read it from the standard input.
+        file: PathBuf,
+        /// Exit with an error if there are warnings.
+        #[clap(long, short = 'D', env = "DICEWARE_DENY_WARNINGS",
+            action,
+            value_parser = BoolishValueParser::new())]
+        deny_warnings: bool,
+        #[clap(flatten)]
+        load: LoadArgs,
+    },
+    /// List the embedded and registered word lists.
+    Ls {
+        #[clap(flatten)]
+        load: LoadArgs,
+    },
+    /// Show statistics about a word list.
+    Stats {
+        #[clap(flatten)]
+        list: ListArgs,
+        /// The number of words used to estimate the entropy loss without a
+        /// separator.
+        #[clap(long, env = "DICEWARE_STATS_WORDS", default_value = "6")]
+        words: usize,
+    },
+}
+
+impl Cli {
+    /// Returns the passphrase generation options, if generating a passphrase.
+    const fn generate_args(&self) -> Option<&GenerateArgs> {
+        match &self.command {
+            Some(Command::Generate(args)) => Some(args),
+            Some(_) => None,
+            None => Some(&self.generate),
+        }
+    }
+}
+
+impl Command {
+    /// Completes the options with the environment and the configuration.
+    fn apply(
+        &mut self,
+        config: &config::Config,
+        resolver: &mut Resolver<'_>,
+    ) -> Result<(), Failure> {
+        match self {
+            Self::Generate(args) => args.apply(config, resolver)?,
+            Self::Encode { list, .. }
+            | Self::Verify { list, .. }
+            | Self::Decode { list, .. }
+            | Self::List {
+                command: ListCommand::Stats { list, .. },
+            } => list.apply(config, resolver)?,
+            Self::List {
+                command:
+                    ListCommand::Check { load, .. } | ListCommand::Ls { load },
+            } => load.apply(&config.load_options, resolver),
+        }
+
+        Ok(())
+    }
+}
+
+impl GenerateArgs {
+    /// Completes the options with the environment and the configuration.
+    fn apply(
+        &mut self,
+        config: &config::Config,
+        resolver: &mut Resolver<'_>,
+    ) -> Result<(), Failure> {
+        let words = self.words.zip(resolver.explicit("words"));
+        self.words = resolver.resolve(
+            "words",
+            words.map(|(words, origin)| (Some(words), origin)),
+            config.words.map(Some),
+            None,
+            |words| {
+                words.map_or_else(|| String::from("none"), |w| w.to_string())
+            },
+        );
+
+        self.list.apply(config, resolver)?;
+
+        let special_char = resolver.pick(vec![
+            ("with-special-char", Some(self.with_special_char)),
+            ("no-special-char", self.no_special_char.then_some(false)),
+        ])?;
+        self.with_special_char = resolver.resolve(
+            "special char",
+            special_char,
+            config.with_special_char,
+            false,
+            ToString::to_string,
+        );
+
+        let checksum = resolver.pick(vec![
+            ("checksum", Some(self.checksum)),
+            ("no-checksum", self.no_checksum.then_some(false)),
+        ])?;
+        self.checksum = resolver.resolve(
+            "checksum word",
+            checksum,
+            config.checksum_word,
+            false,
+            ToString::to_string,
+        );
+
+        let separator =
+            self.separator.take().zip(resolver.explicit("separator"));
+        self.separator = Some(resolver.resolve(
+            "separator",
+            separator,
+            config.separator.clone(),
+            String::from(" "),
+            |separator| format!("{separator:?}"),
+        ));
+
+        let format = self.format.zip(resolver.explicit("format"));
+        self.format = Some(resolver.resolve(
+            "format",
+            format,
+            config.format,
+            Format::default(),
+            ToString::to_string,
+        ));
+
+        Ok(())
+    }
+}
+
+impl ListArgs {
+    /// Completes the options with the environment and the configuration.
+    ///
+    /// The word list is selected on the command line, else in the
+    /// environment, else in the configuration file, English being the
+    /// default. A list selected by name is then resolved to an embedded list
+    /// or a file from the registry.
+    fn apply(
+        &mut self,
+        config: &config::Config,
+        resolver: &mut Resolver<'_>,
+    ) -> Result<(), Failure> {
+        let embedded = |embedded| ListSetting::Embedded { embedded };
+        let explicit = resolver.pick(vec![
+            (
+                "word-file",
+                self.word_file.take().map(|file| ListSetting::File { file }),
+            ),
+            ("english", self.english.then(|| embedded(EmbeddedList::EN))),
+            ("french", self.french.then(|| embedded(EmbeddedList::FR))),
+            ("list", self.list.take().map(ListSetting::Name)),
+        ])?;
+
+        let (setting, origin) = explicit
+            .or_else(|| {
+                config
+                    .word_list
+                    .clone()
+                    .map(|setting| (setting, resolver.config_file()))
+            })
+            .unwrap_or((
+                ListSetting::Embedded {
+                    embedded: EmbeddedList::EN,
+                },
+                Origin::Default,
+            ));
+
+        self.english = false;
+        self.french = false;
+        let value = match setting {
+            ListSetting::Name(name) => match registry::find(&name)? {
+                Entry::Embedded(list) => {
+                    self.select_embedded(&list);
+                    self.name = name;
+                    self.name.clone()
+                }
+                Entry::File(path) => {
+                    let value = format!("{name} ({})", path.display());
+                    self.word_file = Some(path.to_string_lossy().into_owned());
+                    self.name = name;
+                    value
+                }
+            },
+            ListSetting::File { file } => {
+                self.name = if file == STDIN {
+                    STDIN_NAME.to_owned()
+                } else {
+                    file.clone()
+                };
+                self.word_file = Some(file);
+                self.name.clone()
+            }
+            ListSetting::Embedded { embedded } => {
+                self.select_embedded(&embedded);
+                self.name = registry::name(&embedded).to_owned();
+                self.name.clone()
+            }
+        };
+
+        resolver.record("word list", value, origin);
+        self.load.apply(&config.load_options, resolver);
+        Ok(())
+    }
+
+    /// Selects an embedded word list.
+    fn select_embedded(&mut self, list: &EmbeddedList) {
+        match list {
+            EmbeddedList::EN => self.english = true,
+            EmbeddedList::FR => self.french = true,
+        }
+    }
+
+    /// Returns the selected embedded word list.
+    fn embedded(&self) -> EmbeddedList {
+        if self.french {
             EmbeddedList::FR
         } else {
             EmbeddedList::EN
+        }
+    }
+
+    /// Returns the name of the selected word list, for error messages on
+    /// lists without a path.
+    fn source_name(&self) -> &'static str {
+        match self.word_file.as_deref() {
+            Some(STDIN) => STDIN_NAME,
+            _ => EMBEDDED_NAME,
+        }
+    }
+
+    /// Gets the selected word list.
+    fn word_list(&self) -> Result<WordList, Failure> {
+        let options = self.load.options();
+        let result = match self.word_file.as_deref() {
+            Some(STDIN) => WordList::from_reader(io::stdin().lock(), &options),
+            Some(filename) => {
+                WordList::from_file_with_options(filename, &options)
+            }
+            None => WordList::embedded_with_options(self.embedded(), &options),
         };
 
-        Config::with_embedded(list, cli.words, cli.with_special_char)
-    };
+        result.map_err(|err| Failure::from_error(err, self.source_name()))
+    }
+}
 
-    match diceware::make_passphrase(config) {
-        Ok(passphrase) => println!("{passphrase}"),
-        Err(err) => {
-            let message = match err {
-                Error::IO(e) => {
-                    let word_file = cli
-                        .word_file
-                        .expect("IO error without using a word_file.");
+impl LoadArgs {
+    /// Completes the options with the environment and the configuration.
+    fn apply(
+        &mut self,
+        config: &config::LoadOptions,
+        resolver: &mut Resolver<'_>,
+    ) {
+        let normalization =
+            self.normalization.zip(resolver.explicit("normalization"));
+        self.normalization = Some(resolver.resolve(
+            "normalization",
+            normalization,
+            config.normalization,
+            Normalization::default(),
+            ToString::to_string,
+        ));
 
-                    format!("{word_file}: {e}")
-                }
+        let ascii = resolver.explicit("ascii").map(|o| (self.ascii, o));
+        self.ascii = resolver.resolve(
+            "ascii",
+            ascii,
+            config.ascii_folding,
+            false,
+            ToString::to_string,
+        );
+
+        let strict = resolver.explicit("strict").map(|o| (self.strict, o));
+        self.strict = resolver.resolve(
+            "strict",
+            strict,
+            config.strict,
+            false,
+            ToString::to_string,
+        );
+
+        let encoding = self.encoding.zip(resolver.explicit("encoding"));
+        self.encoding = Some(resolver.resolve(
+            "encoding",
+            encoding,
+            config.encoding,
+            Encoding::default(),
+            ToString::to_string,
+        ));
+    }
+
+    /// Returns the load options.
+    fn options(&self) -> LoadOptions {
+        LoadOptions::default()
+            .normalization(self.normalization.unwrap_or_default())
+            .ascii_folding(self.ascii)
+            .strict(self.strict)
+            .encoding(self.encoding.unwrap_or_default())
+    }
+}
+
+fn main() {
+    let matches = Cli::command()
+        .try_get_matches()
+        .unwrap_or_else(|err| exit_on_clap_error(&err));
+    let mut cli = Cli::from_arg_matches(&matches)
+        .unwrap_or_else(|err| exit_on_clap_error(&err));
+
+    if let Err(failure) = run(&mut cli, &matches) {
+        // The format is resolved when the options are completed. If it fails,
+        // the format set on the command line or in the environment, if any,
+        // is used.
+        let format = cli.generate_args().and_then(|args| args.format);
+        failure.print(format.unwrap_or_default());
+        process::exit(failure.exit_code());
+    }
+}
+
+/// Completes the options and runs the command.
+fn run(cli: &mut Cli, matches: &ArgMatches) -> Result<(), Failure> {
+    let config = config::Config::load()
+        .map_err(|message| Failure::new(Kind::Config, message))?;
+    let mut resolver =
+        Resolver::new(leaf_matches(matches), config.path.clone());
 
-                Error::WordList(e) => e.to_string(),
-                Error::NoWords => err.to_string(),
-            };
+    match &mut cli.command {
+        Some(command) => command.apply(&config, &mut resolver)?,
+        None => cli.generate.apply(&config, &mut resolver)?,
+    }
 
-            eprintln!(
-                "{} {message}",
-                "error:".if_supports_color(Stderr, |text| {
-                    text.style(Style::new().red().bold())
-                })
+    if cli.generate_args().is_some_and(|args| args.show_config) {
+        for setting in resolver.settings() {
+            let message = format!(
+                "{}: {} ({})",
+                setting.name, setting.value, setting.origin
             );
-            process::exit(1);
+            print_diagnostic(Severity::Note, &message);
         }
+    }
+
+    match &cli.command {
+        Some(Command::Encode { data, list }) => encode(data, list),
+        Some(Command::Verify { words, list }) => verify(words, list),
+        Some(Command::Decode { words, list }) => decode(words, list),
+        Some(Command::List { command }) => match command {
+            ListCommand::Check {
+                file,
+                deny_warnings,
+                load,
+            } => check(file, *deny_warnings, load),
+            ListCommand::Ls { load } => ls(load),
+            ListCommand::Stats { list, words } => stats(list, *words),
+        },
+        Some(Command::Generate(args)) => generate(args),
+        None => generate(&cli.generate),
+    }
+}
+
+/// Exits on a command-line parsing error.
+///
+/// Invalid arguments exit with `EX_USAGE`, like other usage errors, and are
+/// printed as JSON if this format has been requested. The help and version
+/// are printed as usual.
+fn exit_on_clap_error(err: &clap::Error) -> ! {
+    if err.use_stderr() {
+        let args: Vec<String> = env::args().skip(1).collect();
+        let format = requested_format(&args, env::var("DICEWARE_FORMAT").ok());
+
+        if format == Format::Json {
+            let message = err.to_string();
+            let message = message.lines().next().unwrap_or_default();
+            let message = message.strip_prefix("error: ").unwrap_or(message);
+            Failure::usage(message).print(format);
+        } else {
+            // Nothing more can be done if printing the error fails.
+            let _ = err.print();
+        }
+
+        process::exit(Kind::Usage.exit_code());
+    }
+
+    err.exit()
+}
+
+/// Returns the output format requested on the command line or in the
+/// environment, for arguments that cannot be parsed.
+///
+/// Only `--format FORMAT` and `--format=FORMAT` are recognized. An invalid
+/// format is ignored.
+fn requested_format(args: &[String], env_format: Option<String>) -> Format {
+    let mut value = None;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break;
+        } else if arg == "--format" {
+            value = args.next().cloned();
+        } else if let Some(format) = arg.strip_prefix("--format=") {
+            value = Some(format.to_owned());
+        }
+    }
+
+    value
+        .or(env_format)
+        .and_then(|format| Format::from_str(&format, true).ok())
+        .unwrap_or_default()
+}
+
+/// Returns the matches of the innermost subcommand.
+fn leaf_matches(matches: &ArgMatches) -> &ArgMatches {
+    match matches.subcommand() {
+        Some((_, matches)) => leaf_matches(matches),
+        None => matches,
+    }
+}
+
+/// Prints a diagnostic message on stderr.
+fn print_diagnostic(severity: Severity, message: &str) {
+    let style = match severity {
+        Severity::Error => Style::new().red().bold(),
+        Severity::Warning => Style::new().yellow().bold(),
+        Severity::Note => Style::new().cyan().bold(),
     };
+
+    eprintln!(
+        "{} {message}",
+        format!("{severity}:")
+            .if_supports_color(Stderr, |text| { text.style(style) })
+    );
+}
+
+/// Generates a passphrase.
+fn generate(args: &GenerateArgs) -> Result<(), Failure> {
+    let words = args.words.ok_or_else(|| {
+        Failure::usage(
+            "missing number of words: pass it as an argument or set `words` \
+             in the configuration file",
+        )
+    })?;
+
+    let config = match args.list.word_file.as_deref() {
+        Some(STDIN) => {
+            Config::with_reader(io::stdin(), words, args.with_special_char)
+        }
+        Some(filename) => {
+            Config::with_filename(filename, words, args.with_special_char)
+        }
+        None => Config::with_embedded(
+            args.list.embedded(),
+            words,
+            args.with_special_char,
+        ),
+    }
+    .checksum_word(args.checksum)
+    .separator(args.separator.as_deref().unwrap_or(" "))
+    .load_options(args.list.load.options());
+
+    // The word list is fingerprinted once loaded, so that it is the one
+    // actually used, even when read from the standard input.
+    let result = match args.insecure_seed {
+        Some(seed) => diceware::insecure::passphrases(config, seed).map(
+            |mut passphrases| {
+                let fingerprint = hex(&passphrases.word_list().fingerprint());
+                (passphrases.next_passphrase(), fingerprint)
+            },
+        ),
+        None => diceware::passphrases(config).map(|mut passphrases| {
+            let fingerprint = hex(&passphrases.word_list().fingerprint());
+            (passphrases.next_passphrase(), fingerprint)
+        }),
+    };
+
+    let (passphrase, fingerprint) = result
+        .map_err(|err| Failure::from_error(err, args.list.source_name()))?;
+    let generated = Generated::new(&passphrase, &args.list.name, fingerprint);
+    let output = generated
+        .format(args.format.unwrap_or_default())
+        .map_err(|message| Failure::new(Kind::Internal, message))?;
+    println!("{output}");
+
+    Ok(())
+}
+
+/// Verifies the checksum word of a passphrase.
+fn verify(words: &[String], list: &ListArgs) -> Result<(), Failure> {
+    let word_list = list.word_list()?;
+    let valid = word_list
+        .verify_checksum(&words.join(" "))
+        .map_err(|err| Failure::from_error(err, list.source_name()))?;
+
+    if valid {
+        println!("valid checksum");
+        Ok(())
+    } else {
+        Err(Failure::data("invalid checksum"))
+    }
+}
+
+/// Encodes hexadecimal data as words.
+fn encode(data: &str, list: &ListArgs) -> Result<(), Failure> {
+    let data = parse_hex(data)?;
+    let word_list = list.word_list()?;
+
+    println!("{}", mnemonic::encode(&word_list, &data));
+
+    Ok(())
+}
+
+/// Decodes words to hexadecimal data.
+fn decode(words: &[String], list: &ListArgs) -> Result<(), Failure> {
+    let word_list = list.word_list()?;
+    let data = mnemonic::decode(&word_list, &words.join(" "))
+        .map_err(|err| Failure::from_error(err, list.source_name()))?;
+
+    println!("{}", hex(&data));
+
+    Ok(())
+}
+
+/// Checks a word list file.
+fn check(
+    file: &Path,
+    deny_warnings: bool,
+    load: &LoadArgs,
+) -> Result<(), Failure> {
+    let options = load.options();
+    let (source, result) = if file == Path::new(STDIN) {
+        let result = diceware::check_reader(io::stdin().lock(), &options);
+        (STDIN_NAME.to_owned(), result)
+    } else {
+        let result = diceware::check_file_with_options(file, &options);
+        (file.display().to_string(), result)
+    };
+
+    let diagnostics =
+        result.map_err(|err| Failure::from_error(err, &source))?;
+
+    for diagnostic in &diagnostics {
+        let severity = diagnostic.kind.severity();
+        let message = match diagnostic.line {
+            Some(line) => format!("{source}:{line}: {}", diagnostic.kind),
+            None => format!("{source}: {}", diagnostic.kind),
+        };
+
+        print_diagnostic(severity, &message);
+    }
+
+    let count = |severity| {
+        diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.kind.severity() == severity)
+            .count()
+    };
+
+    let errors = count(Severity::Error);
+    let warnings = count(Severity::Warning);
+
+    let warnings_count = plural(warnings, "warning");
+
+    if errors > 0 || (deny_warnings && warnings > 0) {
+        Err(Failure::data(format!(
+            "{source}: invalid word list ({}, {warnings_count})",
+            plural(errors, "error")
+        )))
+    } else {
+        println!("{source}: valid word list ({warnings_count})");
+        Ok(())
+    }
+}
+
+/// Lists the embedded and registered word lists.
+///
+/// Registered files shadowed by an embedded list are listed too, as they
+/// cannot be selected by name.
+fn ls(load: &LoadArgs) -> Result<(), Failure> {
+    /// The number of hexadecimal digits of the fingerprints to show.
+    const FINGERPRINT_DIGITS: usize = 16;
+
+    let options = load.options();
+    let mut rows = vec![[
+        String::from("NAME"),
+        String::from("WORDS"),
+        String::from("FINGERPRINT"),
+        String::from("LOCATION"),
+    ]];
+
+    for listing in registry::list()? {
+        let (word_list, mut location) = match listing.entry {
+            Entry::Embedded(list) => (
+                WordList::embedded_with_options(list, &options),
+                EMBEDDED_NAME.to_owned(),
+            ),
+            Entry::File(path) => (
+                WordList::from_file_with_options(&path, &options),
+                path.display().to_string(),
+            ),
+        };
+
+        if listing.shadowed {
+            location.push_str(" (shadowed)");
+        }
+
+        let (words, fingerprint) = match word_list {
+            Ok(word_list) => {
+                let mut fingerprint = hex(&word_list.fingerprint());
+                fingerprint.truncate(FINGERPRINT_DIGITS);
+                (word_list.words().len().to_string(), fingerprint)
+            }
+            Err(_) => (String::from("-"), String::from("(invalid)")),
+        };
+
+        rows.push([listing.name, words, fingerprint, location]);
+    }
+
+    let widths: Vec<usize> = (0..3)
+        .map(|i| rows.iter().map(|row| row[i].chars().count()).max())
+        .map(Option::unwrap_or_default)
+        .collect();
+
+    for [name, words, fingerprint, location] in &rows {
+        println!(
+            "{name:<0$}  {words:>1$}  {fingerprint:<2$}  {location}",
+            widths[0], widths[1], widths[2]
+        );
+    }
+
+    Ok(())
+}
+
+/// Shows statistics about a word list.
+fn stats(list: &ListArgs, words: usize) -> Result<(), Failure> {
+    const SAMPLES: usize = 1000;
+
+    let word_list = list.word_list()?;
+    let stats = word_list.stats();
+
+    println!("fingerprint: {}", hex(&word_list.fingerprint()));
+    let chars = |set: &BTreeSet<char>| -> String {
+        if set.is_empty() {
+            String::from("(none)")
+        } else {
+            set.iter().collect()
+        }
+    };
+
+    println!(
+        "length: min {}, average {:.2}, max {}",
+        stats.min_length, stats.average_length, stats.max_length
+    );
+    println!("characters: {}", chars(&stats.charset));
+    println!("non-ASCII characters: {}", chars(&stats.non_ascii));
+    println!(
+        "non-alphabetic words: {}{}",
+        stats.non_alphabetic.len(),
+        examples(&stats.non_alphabetic)
+    );
+    println!("prefixes of other words: {}", stats.prefix_words);
+    println!(
+        "words sharing their first {} characters: {}",
+        diceware::PREFIX_LENGTH,
+        stats.shared_prefixes
+    );
+    println!(
+        "minimum edit distance: {} ({:?}, {:?})",
+        stats.min_edit_distance, stats.closest_words.0, stats.closest_words.1
+    );
+
+    if word_list.is_uniquely_decodable("") {
+        println!("uniquely decodable without separator: yes");
+    } else {
+        println!(
+            "uniquely decodable without separator: no (estimated entropy loss \
+             for {words} words: {:.2} bits)",
+            word_list.entropy_loss(words, "", SAMPLES)
+        );
+    }
+
+    Ok(())
+}
+
+/// Formats the first words of a list as examples.
+fn examples(words: &[String]) -> String {
+    const MAX_EXAMPLES: usize = 10;
+
+    if words.is_empty() {
+        return String::new();
+    }
+
+    let examples: Vec<String> = words
+        .iter()
+        .take(MAX_EXAMPLES)
+        .map(|word| format!("{word:?}"))
+        .collect();
+    let ellipsis = if words.len() > MAX_EXAMPLES {
+        ", …"
+    } else {
+        ""
+    };
+
+    format!(" ({}{ellipsis})", examples.join(", "))
+}
+
+/// Formats a count followed by a noun, in the plural unless the count is 1.
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("{count} {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+/// Formats data in hexadecimal.
+fn hex(data: &[u8]) -> String {
+    data.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Parses hexadecimal data.
+fn parse_hex(data: &str) -> Result<Vec<u8>, Failure> {
+    let invalid =
+        || Failure::usage(format!("{data}: invalid hexadecimal data"));
+
+    if !data.len().is_multiple_of(2) || !data.is_ascii() {
+        return Err(invalid());
+    }
+
+    (0..data.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&data[i..i + 2], 16).map_err(|_| invalid()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_requested_format_in_unparsed_arguments() {
+        let args = |args: &[&str]| -> Vec<String> {
+            args.iter().map(|&arg| arg.to_owned()).collect()
+        };
+        let json = Some(String::from("json"));
+
+        assert_eq!(
+            requested_format(&args(&["--format", "json", "--bogus"]), None),
+            Format::Json
+        );
+        assert_eq!(
+            requested_format(&args(&["--format=yaml", "6"]), json.clone()),
+            Format::Yaml
+        );
+        assert_eq!(requested_format(&args(&["--bogus"]), json), Format::Json);
+        assert_eq!(
+            requested_format(&args(&["--", "--format", "json"]), None),
+            Format::Text
+        );
+        assert_eq!(
+            requested_format(&args(&["--format", "xml"]), None),
+            Format::Text
+        );
+    }
+
+    #[test]
+    fn pluralizes_counts() {
+        assert_eq!(plural(0, "error"), "0 errors");
+        assert_eq!(plural(1, "error"), "1 error");
+        assert_eq!(plural(2, "warning"), "2 warnings");
+    }
 }
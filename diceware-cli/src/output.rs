@@ -0,0 +1,241 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018, 2022 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Output formats.
+
+use std::fmt;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use diceware::{InsertedSpecialChar, Passphrase};
+
+/// An output format.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// The passphrase alone.
+    #[default]
+    Text,
+    /// A JSON object.
+    Json,
+    /// A YAML document.
+    Yaml,
+    /// A header line and a line of tab-separated values.
+    Tsv,
+}
+
+/// A generated passphrase, with its structure and the word list it comes
+/// from.
+#[derive(Debug, Serialize)]
+pub struct Generated<'a> {
+    /// The passphrase.
+    pub passphrase: String,
+    /// The words of the passphrase, without the checksum word.
+    pub words: &'a [String],
+    /// The number of words, without the checksum word.
+    pub word_count: usize,
+    /// The special character inserted in a word, if any.
+    pub special_char: Option<InsertedSpecialChar>,
+    /// The checksum word, if any.
+    pub checksum_word: Option<&'a str>,
+    /// The separator between words.
+    pub separator: &'a str,
+    /// The entropy of the passphrase, in bits.
+    pub entropy: f64,
+    /// The word list.
+    pub list: List<'a>,
+}
+
+/// The word list a passphrase comes from.
+#[derive(Debug, Serialize)]
+pub struct List<'a> {
+    /// The name of the list, or its path.
+    pub name: &'a str,
+    /// The fingerprint of the list, in hexadecimal.
+    pub fingerprint: String,
+}
+
+impl<'a> Generated<'a> {
+    /// Describes a generated passphrase.
+    pub fn new(
+        passphrase: &'a Passphrase,
+        list: &'a str,
+        fingerprint: String,
+    ) -> Self {
+        Self {
+            passphrase: passphrase.to_string(),
+            words: &passphrase.words,
+            word_count: passphrase.words.len(),
+            special_char: passphrase.special_char,
+            checksum_word: passphrase.checksum_word.as_deref(),
+            separator: &passphrase.separator,
+            entropy: passphrase.entropy,
+            list: List {
+                name: list,
+                fingerprint,
+            },
+        }
+    }
+
+    /// Formats the passphrase.
+    pub fn format(&self, format: Format) -> Result<String, String> {
+        match format {
+            Format::Text => Ok(self.passphrase.clone()),
+            Format::Json => {
+                serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+            }
+            Format::Yaml => serde_yaml_ng::to_string(self)
+                .map(|yaml| yaml.trim_end().to_owned())
+                .map_err(|e| e.to_string()),
+            Format::Tsv => Ok(self.tsv()),
+        }
+    }
+
+    /// Formats the passphrase as tab-separated values.
+    ///
+    /// Words are separated by spaces, and tabs, line feeds, carriage returns
+    /// and backslashes in values are escaped.
+    fn tsv(&self) -> String {
+        let special_char = |field: fn(&InsertedSpecialChar) -> String| {
+            self.special_char.as_ref().map_or_else(String::new, field)
+        };
+
+        let fields = [
+            ("passphrase", escape(&self.passphrase)),
+            ("words", escape(&self.words.join(" "))),
+            ("word_count", self.word_count.to_string()),
+            (
+                "special_char",
+                special_char(|s| escape(&s.character.to_string())),
+            ),
+            ("special_char_word", special_char(|s| s.word.to_string())),
+            (
+                "special_char_position",
+                special_char(|s| s.position.to_string()),
+            ),
+            (
+                "checksum_word",
+                escape(self.checksum_word.unwrap_or_default()),
+            ),
+            ("separator", escape(self.separator)),
+            ("entropy", self.entropy.to_string()),
+            ("list", escape(self.list.name)),
+            ("fingerprint", self.list.fingerprint.clone()),
+        ];
+
+        let (header, values): (Vec<&str>, Vec<String>) =
+            fields.into_iter().unzip();
+        format!("{}\n{}", header.join("\t"), values.join("\t"))
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text => write!(f, "text"),
+            Self::Json => write!(f, "json"),
+            Self::Yaml => write!(f, "yaml"),
+            Self::Tsv => write!(f, "tsv"),
+        }
+    }
+}
+
+/// Escapes a TSV value.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn passphrase() -> Passphrase {
+        Passphrase {
+            words: vec![String::from("a1"), String::from("b")],
+            special_char: Some(InsertedSpecialChar {
+                word: 0,
+                character: '1',
+                position: 1,
+            }),
+            checksum_word: Some(String::from("c")),
+            separator: String::from("\t"),
+            entropy: 30.5,
+        }
+    }
+
+    #[test]
+    fn formats_json() {
+        let passphrase = passphrase();
+        let generated = Generated::new(&passphrase, "en", String::from("ab"));
+        let json: serde_json::Value =
+            serde_json::from_str(&generated.format(Format::Json).unwrap())
+                .unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "passphrase": "a1\tb\tc",
+                "words": ["a1", "b"],
+                "word_count": 2,
+                "special_char": { "word": 0, "character": "1", "position": 1 },
+                "checksum_word": "c",
+                "separator": "\t",
+                "entropy": 30.5,
+                "list": { "name": "en", "fingerprint": "ab" },
+            })
+        );
+    }
+
+    #[test]
+    fn formats_yaml() {
+        let passphrase = passphrase();
+        let generated = Generated::new(&passphrase, "en", String::from("ab"));
+        let yaml = generated.format(Format::Yaml).unwrap();
+
+        assert!(yaml.contains("word_count: 2\n"));
+        assert!(yaml.contains("  fingerprint: ab"));
+    }
+
+    #[test]
+    fn formats_tsv_with_escapes() {
+        let passphrase = passphrase();
+        let generated = Generated::new(&passphrase, "en", String::from("ab"));
+
+        assert_eq!(
+            generated.format(Format::Tsv).unwrap(),
+            "passphrase\twords\tword_count\tspecial_char\tspecial_char_word\t\
+             special_char_position\tchecksum_word\tseparator\tentropy\tlist\t\
+             fingerprint\n\
+             a1\\tb\\tc\ta1 b\t2\t1\t0\t1\tc\t\\t\t30.5\ten\tab"
+        );
+    }
+}
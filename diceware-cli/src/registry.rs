@@ -0,0 +1,270 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018, 2022 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The registry of named word lists.
+//!
+//! Word list files dropped into `$XDG_DATA_HOME/diceware/lists/` are
+//! registered under their file name, up to the first dot: `corp-en.txt.gz` is
+//! named `corp-en`. The embedded lists share the same namespace, and take
+//! precedence over registered files with the same name.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use diceware::EmbeddedList;
+
+use crate::{
+    failure::{Failure, Kind},
+    xdg,
+};
+
+/// A word list of the registry.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Entry {
+    /// An embedded word list.
+    Embedded(EmbeddedList),
+    /// A word list file.
+    File(PathBuf),
+}
+
+/// A word list of the registry, as listed by [`list`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct Listing {
+    /// The name of the list.
+    pub name: String,
+    /// The list.
+    pub entry: Entry,
+    /// Whether the list is a file shadowed by an embedded list.
+    pub shadowed: bool,
+}
+
+/// The names of the embedded lists.
+const EMBEDDED: [(&str, EmbeddedList); 2] =
+    [("en", EmbeddedList::EN), ("fr", EmbeddedList::FR)];
+
+/// Returns the directory of the registry.
+pub fn dir() -> Option<PathBuf> {
+    Some(xdg::data_home()?.join("diceware").join("lists"))
+}
+
+/// Lists the word lists of the registry, sorted by name.
+///
+/// Files shadowed by an embedded list are listed right after it.
+///
+/// # Errors
+///
+/// This function returns an error if the registry directory exists but cannot
+/// be read.
+pub fn list() -> Result<Vec<Listing>, Failure> {
+    let files = match dir() {
+        Some(dir) => files(&dir)?,
+        None => vec![],
+    };
+
+    Ok(listings(files))
+}
+
+/// Finds a word list by name.
+///
+/// # Errors
+///
+/// This function returns an error if there is no list with this name, or if
+/// several files have this name.
+pub fn find(name: &str) -> Result<Entry, Failure> {
+    if let Some(list) = embedded(name) {
+        return Ok(Entry::Embedded(list));
+    }
+
+    let dir = dir().ok_or_else(|| unknown(name))?;
+    let paths: Vec<PathBuf> = files(&dir)?
+        .into_iter()
+        .filter(|(file_name, _)| file_name == name)
+        .map(|(_, path)| path)
+        .collect();
+
+    match &paths[..] {
+        [] => Err(unknown(name)),
+        [path] => Ok(Entry::File(path.clone())),
+        _ => Err(Failure::usage(format!(
+            "several word lists are named {name:?}: {}",
+            paths
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+/// Returns the name of an embedded list.
+pub fn name(list: &EmbeddedList) -> &'static str {
+    match list {
+        EmbeddedList::EN => "en",
+        EmbeddedList::FR => "fr",
+    }
+}
+
+/// Lists the embedded lists and the given files, sorted by name.
+fn listings(files: Vec<(String, PathBuf)>) -> Vec<Listing> {
+    let embedded_lists = EMBEDDED.iter().map(|(name, list)| Listing {
+        name: (*name).to_owned(),
+        entry: Entry::Embedded(list.clone()),
+        shadowed: false,
+    });
+
+    let files = files.into_iter().map(|(name, path)| Listing {
+        shadowed: embedded(&name).is_some(),
+        name,
+        entry: Entry::File(path),
+    });
+
+    let mut listings: Vec<Listing> = embedded_lists.chain(files).collect();
+
+    // The sort is stable: embedded lists stay before the files they shadow.
+    listings.sort_by(|a, b| a.name.cmp(&b.name));
+    listings
+}
+
+/// Returns the embedded list with the given name, if any.
+fn embedded(name: &str) -> Option<EmbeddedList> {
+    EMBEDDED
+        .iter()
+        .find(|(embedded, _)| *embedded == name)
+        .map(|(_, list)| list.clone())
+}
+
+/// Lists the word list files in a directory, with their name.
+///
+/// Hidden files and directories are ignored. A missing directory is an empty
+/// registry.
+fn files(dir: &Path) -> Result<Vec<(String, PathBuf)>, Failure> {
+    let read_error = |err: io::Error| {
+        Failure::new(Kind::Io, format!("{}: {err}", dir.display()))
+    };
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Ok(vec![]);
+        }
+        Err(err) => return Err(read_error(err)),
+    };
+
+    let mut files = vec![];
+    for entry in entries {
+        let path = entry.map_err(read_error)?.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        if let Some(name) = list_name(&path) {
+            files.push((name, path));
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Returns the name of a word list file, up to the first dot.
+fn list_name(path: &Path) -> Option<String> {
+    let file_name = path.file_name()?.to_str()?;
+    let name = file_name.split('.').next()?;
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(name.to_owned())
+    }
+}
+
+/// Builds the error for an unknown list.
+fn unknown(name: &str) -> Failure {
+    Failure::usage(format!(
+        "unknown word list {name:?}: see `diceware list ls`"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn names_lists_up_to_the_first_dot() {
+        assert_eq!(
+            list_name(Path::new("/lists/corp-en.txt.gz")).as_deref(),
+            Some("corp-en")
+        );
+        assert_eq!(
+            list_name(Path::new("/lists/corp")).as_deref(),
+            Some("corp")
+        );
+        assert_eq!(list_name(Path::new("/lists/.hidden.txt")), None);
+    }
+
+    #[test]
+    fn lists_the_files_of_a_directory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        fs::create_dir_all(dir.join("subdir")).unwrap();
+        fs::write(dir.join("b.txt"), "").unwrap();
+        fs::write(dir.join("a.txt.gz"), "").unwrap();
+        fs::write(dir.join(".hidden"), "").unwrap();
+        let files = files(dir);
+
+        assert_eq!(
+            files.unwrap(),
+            vec![
+                (String::from("a"), dir.join("a.txt.gz")),
+                (String::from("b"), dir.join("b.txt")),
+            ]
+        );
+    }
+
+    #[test]
+    fn treats_a_missing_directory_as_empty() {
+        assert_eq!(files(Path::new("/nonexistent/lists")).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn lists_shadowed_files_after_the_embedded_list() {
+        let files = vec![
+            (String::from("corp"), PathBuf::from("/lists/corp.txt")),
+            (String::from("fr"), PathBuf::from("/lists/fr.txt")),
+        ];
+
+        let listings = listings(files);
+        let listed: Vec<(&str, bool)> = listings
+            .iter()
+            .map(|listing| (listing.name.as_str(), listing.shadowed))
+            .collect();
+
+        assert_eq!(
+            listed,
+            vec![("corp", false), ("en", false), ("fr", false), ("fr", true)]
+        );
+        assert_eq!(listings[2].entry, Entry::Embedded(EmbeddedList::FR));
+    }
+
+    #[test]
+    fn finds_embedded_lists_by_name() {
+        assert_eq!(find("fr").unwrap(), Entry::Embedded(EmbeddedList::FR));
+    }
+}
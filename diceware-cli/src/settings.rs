@@ -0,0 +1,297 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018, 2022 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The resolution of the effective settings.
+//!
+//! Each option is taken, by order of precedence, from the command line, the
+//! environment, the configuration file or the built-in default.
+
+use std::{fmt, path::PathBuf};
+
+use clap::{ArgMatches, CommandFactory, ValueSource};
+
+use crate::{failure::Failure, Cli};
+
+/// Where the effective value of an option comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Origin {
+    /// The command line.
+    CommandLine,
+    /// An environment variable.
+    Environment(String),
+    /// The configuration file.
+    ConfigFile(Option<PathBuf>),
+    /// The built-in default.
+    Default,
+}
+
+/// The effective value of an option.
+#[derive(Debug)]
+pub struct Setting {
+    /// The name of the option.
+    pub name: &'static str,
+    /// The value, formatted for humans.
+    pub value: String,
+    /// Where the value comes from.
+    pub origin: Origin,
+}
+
+/// A resolver of the effective settings.
+///
+/// It records every resolved value with its origin, to be shown with
+/// `--show-config`.
+#[derive(Debug)]
+pub struct Resolver<'a> {
+    matches: &'a ArgMatches,
+    config_path: Option<PathBuf>,
+    settings: Vec<Setting>,
+}
+
+impl<'a> Resolver<'a> {
+    /// Creates a resolver for the matches of a command.
+    pub const fn new(
+        matches: &'a ArgMatches,
+        config_path: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            matches,
+            config_path,
+            settings: vec![],
+        }
+    }
+
+    /// Returns the origin of an argument, if it has been set on the command
+    /// line or in the environment.
+    pub fn explicit(&self, id: &str) -> Option<Origin> {
+        match self.matches.value_source(id)? {
+            ValueSource::CommandLine => Some(Origin::CommandLine),
+            ValueSource::EnvVariable => Some(Origin::Environment(env_name(id))),
+            _ => None,
+        }
+    }
+
+    /// Picks the value set with the highest precedence by options setting the
+    /// same value, like `--en` and `--fr`.
+    ///
+    /// Each candidate is an argument with the value it sets, if any. As these
+    /// options cannot be declared as overriding each other without conflicting
+    /// with environment variables, they are checked here.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if several options are set on the
+    /// command line, or in the environment.
+    pub fn pick<T>(
+        &self,
+        candidates: Vec<(&str, Option<T>)>,
+    ) -> Result<Option<(T, Origin)>, Failure> {
+        let mut set: Vec<(&str, T, Origin)> = candidates
+            .into_iter()
+            .filter_map(|(id, value)| Some((id, value?, self.explicit(id)?)))
+            .collect();
+
+        if set
+            .iter()
+            .any(|(_, _, origin)| *origin == Origin::CommandLine)
+        {
+            set.retain(|(_, _, origin)| *origin == Origin::CommandLine);
+        }
+
+        match &set[..] {
+            [(a, _, origin_a), (b, _, origin_b), ..] => {
+                Err(Failure::usage(format!(
+                    "{} cannot be used with {}",
+                    source_name(a, origin_a),
+                    source_name(b, origin_b)
+                )))
+            }
+            _ => Ok(set.pop().map(|(_, value, origin)| (value, origin))),
+        }
+    }
+
+    /// Resolves the value of an option, and records it.
+    ///
+    /// `explicit` is the value set on the command line or in the environment,
+    /// if any, and `config` the value from the configuration file.
+    pub fn resolve<T>(
+        &mut self,
+        name: &'static str,
+        explicit: Option<(T, Origin)>,
+        config: Option<T>,
+        default: T,
+        show: impl Fn(&T) -> String,
+    ) -> T {
+        let (value, origin) = explicit
+            .or_else(|| {
+                config.map(|value| {
+                    (value, Origin::ConfigFile(self.config_path.clone()))
+                })
+            })
+            .unwrap_or((default, Origin::Default));
+
+        self.record(name, show(&value), origin);
+        value
+    }
+
+    /// Records a value resolved by the caller.
+    pub fn record(
+        &mut self,
+        name: &'static str,
+        value: String,
+        origin: Origin,
+    ) {
+        self.settings.push(Setting {
+            name,
+            value,
+            origin,
+        });
+    }
+
+    /// Returns the origin of values from the configuration file.
+    pub fn config_file(&self) -> Origin {
+        Origin::ConfigFile(self.config_path.clone())
+    }
+
+    /// Returns the recorded settings.
+    pub fn settings(&self) -> &[Setting] {
+        &self.settings
+    }
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CommandLine => write!(f, "command line"),
+            Self::Environment(var) => write!(f, "environment variable {var}"),
+            Self::ConfigFile(Some(path)) => {
+                write!(f, "configuration file {}", path.display())
+            }
+            Self::ConfigFile(None) => write!(f, "configuration file"),
+            Self::Default => write!(f, "default"),
+        }
+    }
+}
+
+/// Returns the name of the environment variable of an argument.
+fn env_name(id: &str) -> String {
+    Cli::command()
+        .get_arguments()
+        .find(|arg| arg.get_id() == id)
+        .and_then(|arg| arg.get_env())
+        .map_or_else(String::new, |var| var.to_string_lossy().into_owned())
+}
+
+/// Returns the name of an argument as set from an origin, like `--en` or
+/// `DICEWARE_LIST`.
+fn source_name(id: &str, origin: &Origin) -> String {
+    match origin {
+        Origin::Environment(var) => var.clone(),
+        _ => Cli::command()
+            .get_arguments()
+            .find(|arg| arg.get_id() == id)
+            .and_then(|arg| arg.get_long())
+            .map_or_else(|| id.to_owned(), |long| format!("--{long}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::{config::Config, GenerateArgs};
+    use clap::FromArgMatches;
+
+    /// The origin of each setting.
+    type Origins = Vec<(&'static str, Origin)>;
+
+    /// Parses arguments and applies a configuration, returning the generation
+    /// options and the origin of each setting.
+    fn apply(
+        args: &[&str],
+        config: &str,
+    ) -> Result<(GenerateArgs, Origins), Failure> {
+        let matches = Cli::command().try_get_matches_from(args).unwrap();
+        let mut cli = Cli::from_arg_matches(&matches).unwrap();
+        let config: Config = toml::from_str(config).unwrap();
+
+        let mut resolver = Resolver::new(&matches, None);
+        cli.generate.apply(&config, &mut resolver)?;
+        let origins = resolver
+            .settings()
+            .iter()
+            .map(|setting| (setting.name, setting.origin.clone()))
+            .collect();
+
+        Ok((cli.generate, origins))
+    }
+
+    /// Returns the origin of a setting.
+    fn origin(origins: &[(&str, Origin)], name: &str) -> Origin {
+        origins
+            .iter()
+            .find(|(setting, _)| *setting == name)
+            .map(|(_, origin)| origin.clone())
+            .unwrap()
+    }
+
+    #[test]
+    fn gives_precedence_to_the_command_line() {
+        let config = "word_list = \"en\"\nwords = 8\nwith_special_char = true";
+        let (args, origins) =
+            apply(&["diceware", "--fr", "--no-special-char", "4"], config)
+                .unwrap();
+
+        assert!(args.list.french);
+        assert!(!args.list.english);
+        assert_eq!(args.words, Some(4));
+        assert!(!args.with_special_char);
+        assert_eq!(origin(&origins, "word list"), Origin::CommandLine);
+        assert_eq!(origin(&origins, "special char"), Origin::CommandLine);
+    }
+
+    #[test]
+    fn falls_back_to_the_config_then_the_defaults() {
+        let config = "word_list = \"fr\"\nwords = 8\nseparator = \"-\"";
+        let (args, origins) = apply(&["diceware"], config).unwrap();
+
+        assert!(args.list.french);
+        assert_eq!(args.words, Some(8));
+        assert_eq!(args.separator.as_deref(), Some("-"));
+        assert!(!args.with_special_char);
+        assert_eq!(origin(&origins, "words"), Origin::ConfigFile(None));
+        assert_eq!(origin(&origins, "separator"), Origin::ConfigFile(None));
+        assert_eq!(origin(&origins, "special char"), Origin::Default);
+        assert_eq!(origin(&origins, "normalization"), Origin::Default);
+    }
+
+    #[test]
+    fn uses_english_by_default() {
+        let (args, origins) = apply(&["diceware", "6"], "").unwrap();
+
+        assert!(args.list.english);
+        assert_eq!(origin(&origins, "word list"), Origin::Default);
+    }
+
+    #[test]
+    fn refuses_several_word_lists() {
+        let failure =
+            apply(&["diceware", "--en", "--fr", "6"], "").unwrap_err();
+
+        assert_eq!(failure.exit_code(), 64);
+        assert_eq!(failure.message(), "--en cannot be used with --fr");
+    }
+}
@@ -0,0 +1,161 @@
+#ifndef DICEWARE_H
+#define DICEWARE_H
+
+/* Generated with cbindgen. Do not edit this file manually. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The list of embedded word lists.
+//
+// Functions take these values as a `uint32_t`, so that passing any other
+// value from C is an error instead of undefined behaviour.
+typedef enum DicewareEmbeddedList {
+  // The original English Diceware word list.
+  DICEWARE_EMBEDDED_LIST_EN = 0,
+  // Matthieu Weber's French word list.
+  DICEWARE_EMBEDDED_LIST_FR = 1,
+} DicewareEmbeddedList;
+
+// Status codes returned by the functions of the library.
+typedef enum DicewareStatus {
+  // The function has succeeded.
+  DICEWARE_STATUS_OK = 0,
+  // A pointer argument is null.
+  DICEWARE_STATUS_NULL_POINTER = 1,
+  // A string argument is not valid UTF-8, or a passphrase contains a NUL
+  // character.
+  DICEWARE_STATUS_INVALID_STRING = 2,
+  // The word list file cannot be read.
+  DICEWARE_STATUS_IO = 3,
+  // The word list is invalid, or ambiguous with the separator.
+  DICEWARE_STATUS_WORD_LIST = 4,
+  // The number of words to generate is 0.
+  DICEWARE_STATUS_NO_WORDS = 5,
+  // An integer argument is out of range.
+  DICEWARE_STATUS_INVALID_ARGUMENT = 6,
+  // Any other error.
+  DICEWARE_STATUS_OTHER = 255,
+} DicewareStatus;
+
+// A passphrase generator using the operating system random number generator.
+//
+// This type is opaque to C callers. A generator is created with
+// `diceware_generator_new`, and must be freed with
+// `diceware_generator_free`.
+typedef struct DicewareGenerator DicewareGenerator;
+
+// A checked Diceware word list.
+//
+// This type is opaque to C callers. A word list is created with
+// `diceware_word_list_embedded` or
+// `diceware_word_list_from_file`,
+// and must be freed with
+// `diceware_word_list_free`.
+typedef struct DicewareWordList DicewareWordList;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Loads an embedded word list.
+//
+// `list` is a `DicewareEmbeddedList` value. On success, `*out` points to the
+// word list, which must be freed with `diceware_word_list_free`.
+//
+// # Safety
+//
+// `out` must be null or valid for writes.
+enum DicewareStatus diceware_word_list_embedded(uint32_t list, struct DicewareWordList **out);
+
+// Loads a word list from a file.
+//
+// See the library documentation for the file format. On success, `*out`
+// points to the word list, which must be freed with
+// `diceware_word_list_free`.
+//
+// # Safety
+//
+// `path` must be null or a valid NUL-terminated string, and `out` must be
+// null or valid for writes.
+enum DicewareStatus diceware_word_list_from_file(const char *path, struct DicewareWordList **out);
+
+// Frees a word list.
+//
+// Freeing a null pointer does nothing.
+//
+// # Safety
+//
+// `list` must be null or a word list returned by this library, which has not
+// been freed yet.
+void diceware_word_list_free(struct DicewareWordList *list);
+
+// Creates a passphrase generator.
+//
+// Words are joined with `separator`, or with a space if `separator` is null.
+// A special character is inserted in a random word if `special_char` is true.
+// The word list is copied and checked once: it can be freed while the
+// generator is still in use. On success, `*out` points to the generator,
+// which must be freed with `diceware_generator_free`.
+//
+// # Safety
+//
+// `list` must be null or a valid word list returned by this library,
+// `separator` must be null or a valid NUL-terminated string, and `out` must
+// be null or valid for writes.
+enum DicewareStatus diceware_generator_new(const struct DicewareWordList *list,
+                                           size_t words,
+                                           bool special_char,
+                                           const char *separator,
+                                           struct DicewareGenerator **out);
+
+// Generates a passphrase.
+//
+// On success, `*out` points to the passphrase, which must be freed with
+// `diceware_passphrase_free`.
+//
+// # Safety
+//
+// `generator` must be null or a valid generator returned by this library,
+// and `out` must be null or valid for writes.
+enum DicewareStatus diceware_generator_next(struct DicewareGenerator *generator, char **out);
+
+// Frees a passphrase generator.
+//
+// Freeing a null pointer does nothing.
+//
+// # Safety
+//
+// `generator` must be null or a generator returned by this library, which
+// has not been freed yet.
+void diceware_generator_free(struct DicewareGenerator *generator);
+
+// Frees a passphrase, zeroizing its memory.
+//
+// Freeing a null pointer does nothing.
+//
+// # Safety
+//
+// `passphrase` must be null or a passphrase returned by
+// `diceware_generator_next`, which has not been freed yet.
+void diceware_passphrase_free(char *passphrase);
+
+// Returns the entropy of the passphrases generated with `words` words and an
+// optional special character, in bits.
+double diceware_entropy(size_t words, bool special_char);
+
+// Returns the message of the last error on this thread, or null if no error
+// has occurred.
+//
+// The message is valid until the next failing call on this thread. It must
+// not be freed.
+const char *diceware_last_error(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* DICEWARE_H */
@@ -0,0 +1,653 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! C bindings for the Diceware passphrase generator.
+//!
+//! The C header is `include/diceware.h`. It is generated with
+//! [cbindgen](https://github.com/mozilla/cbindgen), and a test checks it is up
+//! to date. To update it, run:
+//!
+//! ```sh
+//! $ DICEWARE_UPDATE_HEADER=1 cargo test -p diceware-ffi
+//! ```
+//!
+//! Every function returning a `DicewareStatus`
+//! records a message describing the error on failure, which can be read with
+//! `diceware_last_error`.
+//!
+//! Passphrases are generated with a `DicewareGenerator`, which checks the word
+//! list and the separator once when it is created. Passphrases returned to C
+//! callers must be freed with `diceware_passphrase_free`, which zeroizes them.
+
+#![warn(rust_2018_idioms)]
+#![warn(clippy::redundant_pub_crate)]
+#![warn(clippy::unwrap_used)]
+#![warn(clippy::use_self)]
+#![deny(missing_docs)]
+#![deny(unused_must_use)]
+#![deny(unsafe_op_in_unsafe_fn)]
+
+use std::{
+    cell::RefCell,
+    ffi::{c_char, CStr, CString},
+    ptr,
+};
+
+use diceware::{Config, EmbeddedList, Error, WordList};
+use zeroize::Zeroize;
+
+/// Status codes returned by the functions of the library.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DicewareStatus {
+    /// The function has succeeded.
+    Ok = 0,
+
+    /// A pointer argument is null.
+    NullPointer = 1,
+
+    /// A string argument is not valid UTF-8, or a passphrase contains a NUL
+    /// character.
+    InvalidString = 2,
+
+    /// The word list file cannot be read.
+    Io = 3,
+
+    /// The word list is invalid, or ambiguous with the separator.
+    WordList = 4,
+
+    /// The number of words to generate is 0.
+    NoWords = 5,
+
+    /// An integer argument is out of range.
+    InvalidArgument = 6,
+
+    /// Any other error.
+    Other = 255,
+}
+
+/// The list of embedded word lists.
+///
+/// Functions take these values as a `uint32_t`, so that passing any other
+/// value from C is an error instead of undefined behaviour.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DicewareEmbeddedList {
+    /// The original English Diceware word list.
+    En = 0,
+
+    /// Matthieu Weber's French word list.
+    Fr = 1,
+}
+
+/// A checked Diceware word list.
+///
+/// This type is opaque to C callers. A word list is created with
+/// `diceware_word_list_embedded` or
+/// `diceware_word_list_from_file`,
+/// and must be freed with
+/// `diceware_word_list_free`.
+pub struct DicewareWordList(WordList);
+
+/// A passphrase generator using the operating system random number generator.
+///
+/// This type is opaque to C callers. A generator is created with
+/// `diceware_generator_new`, and must be freed with
+/// `diceware_generator_free`.
+pub struct DicewareGenerator(Box<dyn Iterator<Item = String>>);
+
+/// An error returned to C callers, with its message.
+struct Failure {
+    status: DicewareStatus,
+    message: String,
+}
+
+thread_local! {
+    /// The message of the last error on this thread.
+    static LAST_ERROR: RefCell<Option<CString>> =
+        const { RefCell::new(None) };
+}
+
+/// Loads an embedded word list.
+///
+/// `list` is a `DicewareEmbeddedList` value. On success, `*out` points to the
+/// word list, which must be freed with `diceware_word_list_free`.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn diceware_word_list_embedded(
+    list: u32,
+    out: *mut *mut DicewareWordList,
+) -> DicewareStatus {
+    // SAFETY: the caller guarantees `out` is null or valid for writes.
+    unsafe {
+        create(out, || {
+            let list = match list {
+                x if x == DicewareEmbeddedList::En as u32 => EmbeddedList::EN,
+                x if x == DicewareEmbeddedList::Fr as u32 => EmbeddedList::FR,
+                _ => {
+                    return Err(Failure {
+                        status: DicewareStatus::InvalidArgument,
+                        message: format!(
+                            "list: invalid embedded list ({list})"
+                        ),
+                    })
+                }
+            };
+
+            Ok(DicewareWordList(WordList::embedded(list)?))
+        })
+    }
+}
+
+/// Loads a word list from a file.
+///
+/// See the library documentation for the file format. On success, `*out`
+/// points to the word list, which must be freed with
+/// `diceware_word_list_free`.
+///
+/// # Safety
+///
+/// `path` must be null or a valid NUL-terminated string, and `out` must be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn diceware_word_list_from_file(
+    path: *const c_char,
+    out: *mut *mut DicewareWordList,
+) -> DicewareStatus {
+    // SAFETY: the caller guarantees `out` is null or valid for writes, and
+    // `path` is null or a valid NUL-terminated string.
+    unsafe {
+        create(out, || {
+            let path = to_str(path, "path")?;
+            Ok(DicewareWordList(WordList::from_file(path)?))
+        })
+    }
+}
+
+/// Frees a word list.
+///
+/// Freeing a null pointer does nothing.
+///
+/// # Safety
+///
+/// `list` must be null or a word list returned by this library, which has not
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn diceware_word_list_free(list: *mut DicewareWordList) {
+    if !list.is_null() {
+        // SAFETY: the caller guarantees `list` has been returned by this
+        // library, i.e. allocated by `Box::into_raw`, and not freed yet.
+        drop(unsafe { Box::from_raw(list) });
+    }
+}
+
+/// Creates a passphrase generator.
+///
+/// Words are joined with `separator`, or with a space if `separator` is null.
+/// A special character is inserted in a random word if `special_char` is true.
+/// The word list is copied and checked once: it can be freed while the
+/// generator is still in use. On success, `*out` points to the generator,
+/// which must be freed with `diceware_generator_free`.
+///
+/// # Safety
+///
+/// `list` must be null or a valid word list returned by this library,
+/// `separator` must be null or a valid NUL-terminated string, and `out` must
+/// be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn diceware_generator_new(
+    list: *const DicewareWordList,
+    words: usize,
+    special_char: bool,
+    separator: *const c_char,
+    out: *mut *mut DicewareGenerator,
+) -> DicewareStatus {
+    // SAFETY: the caller guarantees `out` is null or valid for writes, `list`
+    // is null or valid, and `separator` is null or a valid NUL-terminated
+    // string.
+    unsafe {
+        create(out, || {
+            let Some(DicewareWordList(word_list)) = list.as_ref() else {
+                return Err(null_pointer("list"));
+            };
+
+            let separator = if separator.is_null() {
+                " "
+            } else {
+                to_str(separator, "separator")?
+            };
+
+            let config =
+                Config::with_word_list(word_list.clone(), words, special_char)
+                    .separator(separator);
+
+            Ok(DicewareGenerator(Box::new(diceware::passphrases(config)?)))
+        })
+    }
+}
+
+/// Generates a passphrase.
+///
+/// On success, `*out` points to the passphrase, which must be freed with
+/// `diceware_passphrase_free`.
+///
+/// # Safety
+///
+/// `generator` must be null or a valid generator returned by this library,
+/// and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn diceware_generator_next(
+    generator: *mut DicewareGenerator,
+    out: *mut *mut c_char,
+) -> DicewareStatus {
+    if out.is_null() {
+        return fail(null_pointer("out"));
+    }
+
+    // SAFETY: `out` is not null, and the caller guarantees it is valid for
+    // writes.
+    unsafe { *out = ptr::null_mut() };
+
+    // SAFETY: the caller guarantees `generator` is null or valid.
+    let Some(DicewareGenerator(passphrases)) = (unsafe { generator.as_mut() })
+    else {
+        return fail(null_pointer("generator"));
+    };
+
+    // NOTE(unwrap): the passphrase iterator is infinite.
+    #[allow(clippy::unwrap_used)]
+    let passphrase = passphrases.next().unwrap();
+
+    match into_c_string(passphrase) {
+        Ok(passphrase) => {
+            // SAFETY: `out` is not null, and the caller guarantees it is valid
+            // for writes.
+            unsafe { *out = passphrase.into_raw() };
+            DicewareStatus::Ok
+        }
+
+        Err(failure) => fail(failure),
+    }
+}
+
+/// Frees a passphrase generator.
+///
+/// Freeing a null pointer does nothing.
+///
+/// # Safety
+///
+/// `generator` must be null or a generator returned by this library, which
+/// has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn diceware_generator_free(
+    generator: *mut DicewareGenerator,
+) {
+    if !generator.is_null() {
+        // SAFETY: the caller guarantees `generator` has been returned by this
+        // library, i.e. allocated by `Box::into_raw`, and not freed yet.
+        drop(unsafe { Box::from_raw(generator) });
+    }
+}
+
+/// Frees a passphrase, zeroizing its memory.
+///
+/// Freeing a null pointer does nothing.
+///
+/// # Safety
+///
+/// `passphrase` must be null or a passphrase returned by
+/// `diceware_generator_next`, which has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn diceware_passphrase_free(passphrase: *mut c_char) {
+    if !passphrase.is_null() {
+        // SAFETY: the caller guarantees `passphrase` has been returned by
+        // `diceware_generator_next`, i.e. allocated by `CString::into_raw`, and not
+        // freed yet.
+        let passphrase = unsafe { CString::from_raw(passphrase) };
+        passphrase.into_bytes_with_nul().zeroize();
+    }
+}
+
+/// Returns the entropy of the passphrases generated with `words` words and an
+/// optional special character, in bits.
+#[no_mangle]
+pub extern "C" fn diceware_entropy(words: usize, special_char: bool) -> f64 {
+    Config::with_embedded(EmbeddedList::EN, words, special_char).entropy()
+}
+
+/// Returns the message of the last error on this thread, or null if no error
+/// has occurred.
+///
+/// The message is valid until the next failing call on this thread. It must
+/// not be freed.
+#[no_mangle]
+pub extern "C" fn diceware_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| {
+        last_error
+            .borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Creates an object in `out`.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+unsafe fn create<T>(
+    out: *mut *mut T,
+    create: impl FnOnce() -> Result<T, Failure>,
+) -> DicewareStatus {
+    if out.is_null() {
+        return fail(null_pointer("out"));
+    }
+
+    // SAFETY: `out` is not null, and the caller guarantees it is valid for
+    // writes.
+    unsafe { *out = ptr::null_mut() };
+
+    match create() {
+        Ok(object) => {
+            // SAFETY: `out` is not null, and the caller guarantees it is valid
+            // for writes.
+            unsafe { *out = Box::into_raw(Box::new(object)) };
+            DicewareStatus::Ok
+        }
+
+        Err(failure) => fail(failure),
+    }
+}
+
+/// Converts a C string argument to a string slice.
+///
+/// # Safety
+///
+/// `string` must be null or a valid NUL-terminated string, living for `'a`.
+unsafe fn to_str<'a>(
+    string: *const c_char,
+    name: &str,
+) -> Result<&'a str, Failure> {
+    if string.is_null() {
+        return Err(null_pointer(name));
+    }
+
+    // SAFETY: `string` is not null, and the caller guarantees it is a valid
+    // NUL-terminated string.
+    unsafe { CStr::from_ptr(string) }
+        .to_str()
+        .map_err(|_| Failure {
+            status: DicewareStatus::InvalidString,
+            message: format!("{name}: invalid UTF-8"),
+        })
+}
+
+/// Converts a passphrase to a C string, zeroizing `passphrase`.
+///
+/// Only the copies made here are zeroized: the words the passphrase has been
+/// built from are not.
+fn into_c_string(mut passphrase: String) -> Result<CString, Failure> {
+    // Reserve the space for the NUL terminator, so that the buffer is not
+    // reallocated, leaving a copy of the passphrase in the freed memory.
+    let mut bytes = Vec::with_capacity(passphrase.len() + 1);
+    bytes.extend_from_slice(passphrase.as_bytes());
+    passphrase.zeroize();
+
+    CString::new(bytes).map_err(|err| {
+        err.into_vec().zeroize();
+        Failure {
+            status: DicewareStatus::InvalidString,
+            message: String::from("the passphrase contains a NUL character"),
+        }
+    })
+}
+
+/// Builds a failure for a null pointer argument.
+fn null_pointer(name: &str) -> Failure {
+    Failure {
+        status: DicewareStatus::NullPointer,
+        message: format!("{name}: null pointer"),
+    }
+}
+
+/// Records the message of a failure, returning its status.
+fn fail(failure: Failure) -> DicewareStatus {
+    let message = failure.message.replace('\0', "\\0");
+
+    LAST_ERROR.with(|last_error| {
+        *last_error.borrow_mut() = CString::new(message).ok();
+    });
+
+    failure.status
+}
+
+impl From<Error> for Failure {
+    fn from(err: Error) -> Self {
+        let status = match err {
+            Error::IO { .. } => DicewareStatus::Io,
+            Error::WordList { .. } => DicewareStatus::WordList,
+            Error::NoWords => DicewareStatus::NoWords,
+            _ => DicewareStatus::Other,
+        };
+
+        Self {
+            status,
+            message: err.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn embedded_list() -> *mut DicewareWordList {
+        let mut list = ptr::null_mut();
+        let status = unsafe {
+            diceware_word_list_embedded(
+                DicewareEmbeddedList::En as u32,
+                &mut list,
+            )
+        };
+        assert_eq!(status, DicewareStatus::Ok);
+        list
+    }
+
+    fn last_error() -> String {
+        let message = diceware_last_error();
+        assert!(!message.is_null());
+        unsafe { CStr::from_ptr(message) }
+            .to_str()
+            .unwrap()
+            .to_owned()
+    }
+
+    fn next_passphrase(generator: *mut DicewareGenerator) -> String {
+        let mut passphrase = ptr::null_mut();
+        let status =
+            unsafe { diceware_generator_next(generator, &mut passphrase) };
+        assert_eq!(status, DicewareStatus::Ok);
+
+        let words = unsafe { CStr::from_ptr(passphrase) }
+            .to_str()
+            .unwrap()
+            .to_owned();
+        unsafe { diceware_passphrase_free(passphrase) };
+        words
+    }
+
+    #[test]
+    fn generates_passphrases() {
+        let list = embedded_list();
+        let separator = CString::new("-").unwrap();
+        let mut generator = ptr::null_mut();
+
+        let status = unsafe {
+            diceware_generator_new(
+                list,
+                6,
+                false,
+                separator.as_ptr(),
+                &mut generator,
+            )
+        };
+        assert_eq!(status, DicewareStatus::Ok);
+
+        // The generator has its own copy of the list.
+        unsafe { diceware_word_list_free(list) };
+
+        for _ in 0..3 {
+            assert_eq!(next_passphrase(generator).split('-').count(), 6);
+        }
+
+        unsafe { diceware_generator_free(generator) };
+    }
+
+    #[test]
+    fn joins_words_with_a_space_by_default() {
+        let list = embedded_list();
+        let mut generator = ptr::null_mut();
+
+        let status = unsafe {
+            diceware_generator_new(list, 6, false, ptr::null(), &mut generator)
+        };
+        assert_eq!(status, DicewareStatus::Ok);
+        assert_eq!(next_passphrase(generator).split(' ').count(), 6);
+
+        unsafe {
+            diceware_generator_free(generator);
+            diceware_word_list_free(list);
+        }
+    }
+
+    #[test]
+    fn returns_an_error_if_number_of_words_is_zero() {
+        let list = embedded_list();
+        let mut generator = ptr::null_mut();
+
+        let status = unsafe {
+            diceware_generator_new(list, 0, false, ptr::null(), &mut generator)
+        };
+        assert_eq!(status, DicewareStatus::NoWords);
+        assert!(generator.is_null());
+        assert_eq!(last_error(), "No words to generate");
+
+        unsafe { diceware_word_list_free(list) };
+    }
+
+    #[test]
+    fn returns_an_error_if_the_separator_is_ambiguous() {
+        let list = embedded_list();
+        let separator = CString::new("").unwrap();
+        let mut generator = ptr::null_mut();
+
+        let status = unsafe {
+            diceware_generator_new(
+                list,
+                6,
+                false,
+                separator.as_ptr(),
+                &mut generator,
+            )
+        };
+        assert_eq!(status, DicewareStatus::WordList);
+        assert!(generator.is_null());
+
+        unsafe { diceware_word_list_free(list) };
+    }
+
+    #[test]
+    fn returns_an_error_if_the_embedded_list_is_invalid() {
+        let mut list = ptr::null_mut();
+
+        let status = unsafe { diceware_word_list_embedded(2, &mut list) };
+        assert_eq!(status, DicewareStatus::InvalidArgument);
+        assert!(list.is_null());
+        assert_eq!(last_error(), "list: invalid embedded list (2)");
+    }
+
+    #[test]
+    fn returns_an_error_if_a_pointer_is_null() {
+        let mut generator = ptr::null_mut();
+
+        let status = unsafe {
+            diceware_generator_new(
+                ptr::null(),
+                6,
+                false,
+                ptr::null(),
+                &mut generator,
+            )
+        };
+        assert_eq!(status, DicewareStatus::NullPointer);
+        assert_eq!(last_error(), "list: null pointer");
+
+        let status = unsafe {
+            diceware_generator_next(ptr::null_mut(), &mut ptr::null_mut())
+        };
+        assert_eq!(status, DicewareStatus::NullPointer);
+        assert_eq!(last_error(), "generator: null pointer");
+
+        let status = unsafe {
+            diceware_word_list_from_file(ptr::null(), &mut ptr::null_mut())
+        };
+        assert_eq!(status, DicewareStatus::NullPointer);
+        assert_eq!(last_error(), "path: null pointer");
+    }
+
+    #[test]
+    fn returns_an_error_if_a_string_is_not_utf8() {
+        let path = CString::new(b"\xff.txt".to_vec()).unwrap();
+        let mut list = ptr::null_mut();
+
+        let status =
+            unsafe { diceware_word_list_from_file(path.as_ptr(), &mut list) };
+        assert_eq!(status, DicewareStatus::InvalidString);
+        assert!(list.is_null());
+    }
+
+    #[test]
+    fn returns_an_error_if_the_file_cannot_be_read() {
+        let path = CString::new("/nonexistent/words.txt").unwrap();
+        let mut list = ptr::null_mut();
+
+        let status =
+            unsafe { diceware_word_list_from_file(path.as_ptr(), &mut list) };
+        assert_eq!(status, DicewareStatus::Io);
+        assert!(list.is_null());
+        assert!(last_error().starts_with("/nonexistent/words.txt: "));
+    }
+
+    #[test]
+    fn returns_the_entropy() {
+        let config = Config::with_embedded(EmbeddedList::EN, 6, true);
+        assert_eq!(diceware_entropy(6, true), config.entropy());
+    }
+
+    #[test]
+    fn frees_null_pointers() {
+        unsafe {
+            diceware_word_list_free(ptr::null_mut());
+            diceware_generator_free(ptr::null_mut());
+            diceware_passphrase_free(ptr::null_mut());
+        }
+    }
+}
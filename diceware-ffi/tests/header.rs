@@ -0,0 +1,42 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+#![allow(clippy::unwrap_used)]
+
+use std::{env, fs, path::Path};
+
+#[test]
+fn the_header_is_up_to_date() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let header = crate_dir.join("include").join("diceware.h");
+
+    let config =
+        cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap();
+    let mut generated = vec![];
+    cbindgen::generate_with_config(crate_dir, config)
+        .unwrap()
+        .write(&mut generated);
+
+    if env::var_os("DICEWARE_UPDATE_HEADER").is_some() {
+        fs::write(&header, &generated).unwrap();
+    }
+
+    assert!(
+        fs::read(&header).unwrap() == generated,
+        "include/diceware.h is out of date, run \
+         `DICEWARE_UPDATE_HEADER=1 cargo test -p diceware-ffi` to update it"
+    );
+}
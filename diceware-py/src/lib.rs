@@ -0,0 +1,465 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Python bindings for the Diceware passphrase generator.
+//!
+//! The `diceware` Python module wraps the configuration, the embedded lists,
+//! word list validation and passphrase generation of the library, so that
+//! Python code selects words exactly like the CLI. Errors are raised as Python
+//! exceptions, subclasses of `DicewareError`.
+//!
+//! To build the module in a virtual environment and run its tests, like the
+//! CI does, run:
+//!
+//! ```sh
+//! $ diceware-py/test.sh
+//! ```
+
+#![warn(rust_2018_idioms)]
+#![warn(clippy::redundant_pub_crate)]
+#![warn(clippy::unwrap_used)]
+#![warn(clippy::use_self)]
+#![deny(missing_docs)]
+#![deny(unused_must_use)]
+#![forbid(unsafe_code)]
+
+use pyo3::{create_exception, exceptions::PyException, prelude::*};
+
+create_exception!(
+    diceware,
+    DicewareError,
+    PyException,
+    "Base class of the Diceware errors."
+);
+
+create_exception!(
+    diceware,
+    ReadError,
+    DicewareError,
+    "Raised when a word list cannot be read."
+);
+
+create_exception!(
+    diceware,
+    WordListError,
+    DicewareError,
+    "Raised when a word list cannot be used."
+);
+
+create_exception!(
+    diceware,
+    InvalidWordListError,
+    WordListError,
+    "Raised when a word list is invalid, with every problem found."
+);
+
+create_exception!(
+    diceware,
+    AmbiguousWordListError,
+    WordListError,
+    "Raised when words joined with a separator can be read in several ways."
+);
+
+create_exception!(
+    diceware,
+    WordLookupError,
+    DicewareError,
+    "Raised when a passphrase cannot be looked up in a word list."
+);
+
+create_exception!(
+    diceware,
+    MnemonicError,
+    DicewareError,
+    "Raised when words cannot be decoded to data."
+);
+
+create_exception!(
+    diceware,
+    NoWordsError,
+    DicewareError,
+    "Raised when the number of words to generate is 0."
+);
+
+/// The list of embedded word lists.
+#[pyclass(module = "diceware", eq, eq_int)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmbeddedList {
+    /// The original English Diceware word list.
+    EN,
+
+    /// Matthieu Weber's French word list.
+    FR,
+}
+
+/// A checked Diceware word list.
+#[pyclass(module = "diceware", frozen)]
+#[derive(Clone, Debug)]
+pub struct WordList(diceware::WordList);
+
+/// A problem found in a word list.
+#[pyclass(module = "diceware", frozen, get_all)]
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    /// The line where the problem has been found, if any.
+    line: Option<usize>,
+
+    /// The severity of the problem: `error`, `warning` or `note`.
+    severity: String,
+
+    /// The description of the problem.
+    message: String,
+}
+
+/// Configuration for the passphrase generator.
+#[pyclass(module = "diceware", frozen)]
+#[derive(Clone, Debug)]
+pub struct Config {
+    source: Source,
+    words: usize,
+    with_special_char: bool,
+    checksum_word: bool,
+    separator: String,
+}
+
+/// A word list source.
+#[derive(Clone, Debug)]
+enum Source {
+    File(String),
+    Embedded(EmbeddedList),
+    List(diceware::WordList),
+}
+
+/// An infinite iterator over passphrases.
+#[pyclass(module = "diceware")]
+pub struct Passphrases(diceware::Passphrases<rand::rngs::OsRng>);
+
+#[pymethods]
+impl WordList {
+    /// Creates a word list from a list of words.
+    #[new]
+    fn new(words: Vec<String>) -> PyResult<Self> {
+        Ok(Self(diceware::WordList::new(words).map_err(to_py_err)?))
+    }
+
+    /// Reads a word list from a file.
+    #[staticmethod]
+    fn from_file(py: Python<'_>, filename: &str) -> PyResult<Self> {
+        py.allow_threads(|| diceware::WordList::from_file(filename))
+            .map(Self)
+            .map_err(to_py_err)
+    }
+
+    /// Gets an embedded word list.
+    #[staticmethod]
+    fn embedded(py: Python<'_>, list: EmbeddedList) -> PyResult<Self> {
+        py.allow_threads(|| diceware::WordList::embedded(list.into()))
+            .map(Self)
+            .map_err(to_py_err)
+    }
+
+    /// Returns the words in the list.
+    fn words(&self) -> Vec<String> {
+        self.0.words().to_vec()
+    }
+
+    /// Returns the index of a word in the list, if any.
+    fn index_of(&self, word: &str) -> Option<usize> {
+        self.0.index_of(word)
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.words().len()
+    }
+
+    fn __contains__(&self, word: &str) -> bool {
+        self.0.index_of(word).is_some()
+    }
+}
+
+#[pymethods]
+impl Diagnostic {
+    fn __str__(&self) -> String {
+        match self.line {
+            Some(line) => format!("line {line}: {}", self.message),
+            None => self.message.clone(),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Diagnostic(line={}, severity={:?}, message={:?})",
+            self.line
+                .map_or(String::from("None"), |line| line.to_string()),
+            self.severity,
+            self.message
+        )
+    }
+}
+
+#[pymethods]
+impl Config {
+    /// Creates a configuration using an external word list.
+    #[staticmethod]
+    #[pyo3(signature = (filename, words, with_special_char = false))]
+    fn with_filename(
+        filename: String,
+        words: usize,
+        with_special_char: bool,
+    ) -> Self {
+        Self::new(Source::File(filename), words, with_special_char)
+    }
+
+    /// Creates a configuration using an embedded word list.
+    #[staticmethod]
+    #[pyo3(signature = (list, words, with_special_char = false))]
+    fn with_embedded(
+        list: EmbeddedList,
+        words: usize,
+        with_special_char: bool,
+    ) -> Self {
+        Self::new(Source::Embedded(list), words, with_special_char)
+    }
+
+    /// Creates a configuration using an already loaded word list.
+    #[staticmethod]
+    #[pyo3(signature = (word_list, words, with_special_char = false))]
+    fn with_word_list(
+        word_list: &WordList,
+        words: usize,
+        with_special_char: bool,
+    ) -> Self {
+        Self::new(Source::List(word_list.0.clone()), words, with_special_char)
+    }
+
+    /// Returns a copy of the configuration, appending a checksum word to the
+    /// generated passphrases.
+    fn checksum_word(&self, checksum_word: bool) -> Self {
+        Self {
+            checksum_word,
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of the configuration, joining words with another
+    /// separator.
+    fn separator(&self, separator: String) -> Self {
+        Self {
+            separator,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the entropy of the generated passphrases, in bits.
+    fn entropy(&self) -> f64 {
+        self.to_config().entropy()
+    }
+}
+
+#[pymethods]
+impl Passphrases {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> Option<String> {
+        self.0.next()
+    }
+
+    /// Returns the entropy of the generated passphrases, in bits.
+    fn entropy(&self) -> f64 {
+        self.0.entropy()
+    }
+}
+
+impl Config {
+    /// Creates a configuration with the default options.
+    fn new(source: Source, words: usize, with_special_char: bool) -> Self {
+        Self {
+            source,
+            words,
+            with_special_char,
+            checksum_word: false,
+            separator: String::from(" "),
+        }
+    }
+
+    /// Builds the configuration of the library.
+    fn to_config(&self) -> diceware::Config<'_> {
+        let config = match &self.source {
+            Source::File(filename) => diceware::Config::with_filename(
+                filename,
+                self.words,
+                self.with_special_char,
+            ),
+
+            Source::Embedded(list) => diceware::Config::with_embedded(
+                (*list).into(),
+                self.words,
+                self.with_special_char,
+            ),
+
+            Source::List(word_list) => diceware::Config::with_word_list(
+                word_list.clone(),
+                self.words,
+                self.with_special_char,
+            ),
+        };
+
+        config
+            .checksum_word(self.checksum_word)
+            .separator(&self.separator)
+    }
+}
+
+impl From<EmbeddedList> for diceware::EmbeddedList {
+    fn from(list: EmbeddedList) -> Self {
+        match list {
+            EmbeddedList::EN => Self::EN,
+            EmbeddedList::FR => Self::FR,
+        }
+    }
+}
+
+impl From<&diceware::Diagnostic> for Diagnostic {
+    fn from(diagnostic: &diceware::Diagnostic) -> Self {
+        Self {
+            line: diagnostic.line,
+            severity: diagnostic.kind.severity().to_string(),
+            message: diagnostic.kind.to_string(),
+        }
+    }
+}
+
+// Loading and checking a word list can take a while: the functions below
+// release the GIL meanwhile, so that other Python threads can run.
+
+/// Makes a passphrase given a configuration.
+#[pyfunction]
+fn make_passphrase(py: Python<'_>, config: &Config) -> PyResult<String> {
+    py.allow_threads(|| diceware::make_passphrase(config.to_config()))
+        .map_err(to_py_err)
+}
+
+/// Makes an infinite iterator over passphrases given a configuration.
+///
+/// The word list is loaded and checked only once.
+#[pyfunction]
+fn passphrases(py: Python<'_>, config: &Config) -> PyResult<Passphrases> {
+    py.allow_threads(|| diceware::passphrases(config.to_config()))
+        .map(Passphrases)
+        .map_err(to_py_err)
+}
+
+/// Checks a word list file, returning every problem found.
+#[pyfunction]
+fn check_file(py: Python<'_>, filename: &str) -> PyResult<Vec<Diagnostic>> {
+    let diagnostics = py
+        .allow_threads(|| diceware::check_file(filename))
+        .map_err(to_py_err)?;
+    Ok(diagnostics.iter().map(Diagnostic::from).collect())
+}
+
+/// Converts a library error to a Python exception.
+///
+/// Read and word list errors have a `path` attribute, and invalid word list
+/// errors a `diagnostics` attribute.
+fn to_py_err(err: diceware::Error) -> PyErr {
+    let message = err.to_string();
+
+    Python::with_gil(|py| {
+        let (py_err, path) = match err {
+            diceware::Error::IO { path, .. } => {
+                (ReadError::new_err(message), path)
+            }
+
+            diceware::Error::WordList { path, error } => {
+                let py_err = match error {
+                    diceware::WordListError::Invalid(diagnostics) => {
+                        let py_err = InvalidWordListError::new_err(message);
+                        let diagnostics: Vec<Diagnostic> =
+                            diagnostics.iter().map(Diagnostic::from).collect();
+
+                        if let Err(err) =
+                            py_err.value(py).setattr("diagnostics", diagnostics)
+                        {
+                            return err;
+                        }
+
+                        py_err
+                    }
+
+                    diceware::WordListError::Ambiguous { .. } => {
+                        AmbiguousWordListError::new_err(message)
+                    }
+                };
+
+                (py_err, path)
+            }
+
+            diceware::Error::Lookup(_) => {
+                return WordLookupError::new_err(message)
+            }
+
+            diceware::Error::Mnemonic(_) => {
+                return MnemonicError::new_err(message)
+            }
+
+            diceware::Error::NoWords => return NoWordsError::new_err(message),
+
+            _ => return DicewareError::new_err(message),
+        };
+
+        match py_err.value(py).setattr("path", path) {
+            Ok(()) => py_err,
+            Err(err) => err,
+        }
+    })
+}
+
+/// A Diceware passphrase generator.
+#[pymodule]
+#[pyo3(name = "diceware")]
+fn diceware_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+
+    m.add_class::<EmbeddedList>()?;
+    m.add_class::<WordList>()?;
+    m.add_class::<Diagnostic>()?;
+    m.add_class::<Config>()?;
+    m.add_class::<Passphrases>()?;
+
+    m.add_function(wrap_pyfunction!(make_passphrase, m)?)?;
+    m.add_function(wrap_pyfunction!(passphrases, m)?)?;
+    m.add_function(wrap_pyfunction!(check_file, m)?)?;
+
+    m.add("DicewareError", py.get_type::<DicewareError>())?;
+    m.add("ReadError", py.get_type::<ReadError>())?;
+    m.add("WordListError", py.get_type::<WordListError>())?;
+    m.add(
+        "InvalidWordListError",
+        py.get_type::<InvalidWordListError>(),
+    )?;
+    m.add(
+        "AmbiguousWordListError",
+        py.get_type::<AmbiguousWordListError>(),
+    )?;
+    m.add("WordLookupError", py.get_type::<WordLookupError>())?;
+    m.add("MnemonicError", py.get_type::<MnemonicError>())?;
+    m.add("NoWordsError", py.get_type::<NoWordsError>())?;
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+#!/bin/sh
+#
+# Builds the Python module with maturin in a virtual environment, then runs the
+# Python tests.
+#
+# Usage: diceware-py/test.sh [VENV_DIR]
+
+set -eu
+
+crate_dir="$(cd "$(dirname "$0")" && pwd)"
+venv="${1:-$crate_dir/../target/python-venv}"
+
+if [ ! -d "$venv" ]; then
+    python3 -m venv "$venv"
+    "$venv/bin/pip" install --quiet "maturin>=1.0,<2.0"
+fi
+
+export VIRTUAL_ENV="$(cd "$venv" && pwd)"
+"$VIRTUAL_ENV/bin/maturin" develop --quiet -m "$crate_dir/Cargo.toml"
+"$VIRTUAL_ENV/bin/python" -m unittest discover -s "$crate_dir/tests" -v
@@ -0,0 +1,145 @@
+# diceware - A Diceware passphrase generator.
+# Copyright (C) 2018 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+"""Tests for the diceware Python module."""
+
+import itertools
+import math
+import os
+import tempfile
+import unittest
+
+import diceware
+from diceware import Config, EmbeddedList, WordList
+
+
+def write_list(words):
+    """Writes a word list to a temporary file, returning its path."""
+    file = tempfile.NamedTemporaryFile(
+        "w", suffix=".txt", delete=False, encoding="utf-8"
+    )
+    with file:
+        file.write("\n".join(words))
+    return file.name
+
+
+class TestGeneration(unittest.TestCase):
+    def test_makes_a_passphrase(self):
+        config = Config.with_embedded(EmbeddedList.EN, 8)
+        passphrase = diceware.make_passphrase(config)
+        self.assertEqual(len(passphrase.split(" ")), 8)
+
+    def test_makes_a_passphrase_with_a_special_char(self):
+        config = Config.with_embedded(EmbeddedList.FR, 6, True)
+        passphrase = diceware.make_passphrase(config)
+        self.assertEqual(len(passphrase.split(" ")), 6)
+
+        entropy = 6 * math.log2(7776) + math.log2(36) + math.log2(6)
+        self.assertAlmostEqual(config.entropy(), entropy)
+
+    def test_makes_a_passphrase_with_a_separator_and_a_checksum(self):
+        config = (
+            Config.with_embedded(EmbeddedList.EN, 6)
+            .separator("-")
+            .checksum_word(True)
+        )
+        passphrase = diceware.make_passphrase(config)
+        self.assertEqual(len(passphrase.split("-")), 7)
+
+    def test_makes_a_passphrase_from_a_file(self):
+        path = write_list(str(i) for i in range(7776))
+        try:
+            config = Config.with_filename(path, 6)
+            words = diceware.make_passphrase(config).split(" ")
+            self.assertTrue(all(0 <= int(word) < 7776 for word in words))
+        finally:
+            os.remove(path)
+
+    def test_makes_passphrases(self):
+        word_list = WordList.embedded(EmbeddedList.EN)
+        config = Config.with_word_list(word_list, 6)
+        passphrases = diceware.passphrases(config)
+        for passphrase in itertools.islice(passphrases, 10):
+            self.assertEqual(len(passphrase.split(" ")), 6)
+
+    def test_computes_the_entropy(self):
+        config = Config.with_embedded(EmbeddedList.EN, 6)
+        self.assertAlmostEqual(config.entropy(), 6 * math.log2(7776))
+        passphrases = diceware.passphrases(config)
+        self.assertEqual(passphrases.entropy(), config.entropy())
+
+
+class TestWordList(unittest.TestCase):
+    def test_looks_words_up(self):
+        word_list = WordList.embedded(EmbeddedList.EN)
+        self.assertEqual(len(word_list), 7776)
+        self.assertEqual(word_list.index_of("abbey"), 16)
+        self.assertIsNone(word_list.index_of("zzzzz"))
+        self.assertIn("abbey", word_list)
+
+    def test_creates_a_word_list(self):
+        word_list = WordList([str(i) for i in range(7776)])
+        self.assertEqual(word_list.words()[42], "42")
+
+    def test_checks_a_file(self):
+        path = write_list(["Foo"] + [str(i) for i in range(7775)])
+        try:
+            diagnostics = diceware.check_file(path)
+        finally:
+            os.remove(path)
+
+        self.assertEqual(len(diagnostics), 1)
+        self.assertEqual(diagnostics[0].line, 1)
+        self.assertEqual(diagnostics[0].severity, "warning")
+
+
+class TestErrors(unittest.TestCase):
+    def test_raises_a_read_error(self):
+        with self.assertRaises(diceware.ReadError) as context:
+            WordList.from_file("/nonexistent/words.txt")
+        self.assertEqual(context.exception.path, "/nonexistent/words.txt")
+        self.assertIsInstance(context.exception, diceware.DicewareError)
+
+    def test_raises_an_invalid_word_list_error(self):
+        path = write_list(["a", "a"])
+        try:
+            with self.assertRaises(diceware.InvalidWordListError) as context:
+                WordList.from_file(path)
+        finally:
+            os.remove(path)
+
+        error = context.exception
+        self.assertIsInstance(error, diceware.WordListError)
+        self.assertEqual(error.path, path)
+        self.assertEqual(
+            [(d.line, d.severity) for d in error.diagnostics],
+            [(2, "error"), (None, "error")],
+        )
+
+    def test_raises_an_ambiguous_word_list_error(self):
+        config = Config.with_embedded(EmbeddedList.EN, 6).separator("")
+        with self.assertRaises(diceware.AmbiguousWordListError) as context:
+            diceware.make_passphrase(config)
+        self.assertIsNone(context.exception.path)
+
+    def test_raises_a_no_words_error(self):
+        config = Config.with_embedded(EmbeddedList.EN, 0)
+        with self.assertRaises(diceware.NoWordsError):
+            diceware.make_passphrase(config)
+
+
+if __name__ == "__main__":
+    unittest.main()
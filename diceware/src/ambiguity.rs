@@ -0,0 +1,280 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use alloc::{
+    borrow::ToOwned,
+    collections::{BTreeSet, VecDeque},
+    format,
+    string::String,
+    vec,
+    vec::Vec,
+};
+
+use rand::{prelude::*, rngs::SmallRng};
+
+use crate::WordList;
+
+/// The seed used to sample passphrases when estimating the entropy loss, so
+/// that estimates are reproducible on a given platform.
+const SAMPLING_SEED: u64 = 7776;
+
+impl WordList {
+    /// Returns whether passphrases made of words joined with `separator` can
+    /// be read back in only one way.
+    ///
+    /// When words are joined without a separator, two different word sequences
+    /// can give the same passphrase, like `in` + `to` and `into`, which reduces
+    /// its real entropy. This is checked with the Sardinas–Patterson algorithm
+    /// over the words followed by the separator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::{EmbeddedList, WordList};
+    ///
+    /// let word_list = WordList::embedded(EmbeddedList::EN).unwrap();
+    ///
+    /// assert!(word_list.is_uniquely_decodable(" "));
+    /// assert!(!word_list.is_uniquely_decodable(""));
+    /// ```
+    pub fn is_uniquely_decodable(&self, separator: &str) -> bool {
+        // A character of the separator found in no word marks the end of each
+        // word, which avoids the full check for the usual separators.
+        if separator
+            .chars()
+            .any(|c| !self.words().iter().any(|word| word.contains(c)))
+        {
+            return true;
+        }
+
+        let mut codewords: Vec<String> = self
+            .words()
+            .iter()
+            .map(|word| format!("{word}{separator}"))
+            .collect();
+        codewords.sort_unstable();
+
+        let is_codeword =
+            |suffix: &str| codewords.binary_search_by(|c| c[..].cmp(suffix));
+
+        // The first dangling suffixes are the ones left when a codeword is a
+        // prefix of another one. In a sorted list, they directly follow it.
+        let mut queue: VecDeque<String> = VecDeque::new();
+        for (i, prefix) in codewords.iter().enumerate() {
+            queue.extend(
+                codewords[i + 1..]
+                    .iter()
+                    .take_while(|codeword| codeword.starts_with(&prefix[..]))
+                    .map(|codeword| codeword[prefix.len()..].to_owned()),
+            );
+        }
+
+        let mut seen: BTreeSet<String> = queue.iter().cloned().collect();
+
+        while let Some(suffix) = queue.pop_front() {
+            // A dangling suffix that is a codeword gives two readings.
+            if suffix.is_empty() || is_codeword(&suffix).is_ok() {
+                return false;
+            }
+
+            // Codewords starting with the suffix.
+            let start = is_codeword(&suffix).unwrap_or_else(|i| i);
+            let longer = codewords[start..]
+                .iter()
+                .take_while(|codeword| codeword.starts_with(&suffix[..]))
+                .map(|codeword| &codeword[suffix.len()..]);
+
+            // Codewords the suffix starts with.
+            let shorter = (1..suffix.len())
+                .filter_map(|i| suffix.get(..i).zip(suffix.get(i..)))
+                .filter(|(prefix, _)| is_codeword(prefix).is_ok())
+                .map(|(_, rest)| rest);
+
+            let next: Vec<String> =
+                longer.chain(shorter).map(ToOwned::to_owned).collect();
+
+            for suffix in next {
+                if seen.insert(suffix.clone()) {
+                    queue.push_back(suffix);
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Estimates the entropy lost when making passphrases of `words` words
+    /// joined with `separator`, in bits.
+    ///
+    /// The loss is the average of `log2(n)` over `samples` random passphrases,
+    /// `n` being the number of ways to read a passphrase as `words` words from
+    /// the list. Passphrases are sampled from a fixed seed, so that estimates
+    /// are reproducible on a given platform. The loss is always 0 for a
+    /// uniquely decodable list.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::{EmbeddedList, WordList};
+    ///
+    /// let word_list = WordList::embedded(EmbeddedList::EN).unwrap();
+    ///
+    /// assert_eq!(word_list.entropy_loss(6, " ", 100), 0.0);
+    /// assert!(word_list.entropy_loss(6, "", 100) > 0.0);
+    /// ```
+    pub fn entropy_loss(
+        &self,
+        words: usize,
+        separator: &str,
+        samples: usize,
+    ) -> f64 {
+        if words == 0 || samples == 0 {
+            return 0.0;
+        }
+
+        let mut rng = SmallRng::seed_from_u64(SAMPLING_SEED);
+        let max_length = self.words().iter().map(String::len).max();
+
+        let loss: f64 = (0..samples)
+            .map(|_| {
+                let passphrase = (0..words)
+                    .filter_map(|_| self.words().choose(&mut rng))
+                    .map(AsRef::as_ref)
+                    .collect::<Vec<&str>>()
+                    .join(separator);
+
+                let readings = self.count_readings(
+                    &passphrase,
+                    words,
+                    separator,
+                    max_length.unwrap_or(0),
+                );
+
+                libm::log2(readings)
+            })
+            .sum();
+
+        loss / samples as f64
+    }
+
+    /// Counts the ways to read a passphrase as `words` words joined with
+    /// `separator`.
+    fn count_readings(
+        &self,
+        passphrase: &str,
+        words: usize,
+        separator: &str,
+        max_length: usize,
+    ) -> f64 {
+        // readings[i]: the ways to read passphrase[..i] as k words, k being
+        // the number of iterations.
+        let length = passphrase.len();
+        let mut readings = vec![0.0; length + 1];
+        readings[0] = 1.0;
+
+        for k in 0..words {
+            let mut next = vec![0.0; length + 1];
+
+            for (end, &count) in readings.iter().enumerate() {
+                if count == 0.0 {
+                    continue;
+                }
+
+                let start = if k == 0 {
+                    end
+                } else if passphrase[end..].starts_with(separator) {
+                    end + separator.len()
+                } else {
+                    continue;
+                };
+
+                let ends =
+                    (start..=length.min(start + max_length)).filter(|&i| {
+                        passphrase
+                            .get(start..i)
+                            .is_some_and(|word| self.index_of(word).is_some())
+                    });
+
+                for i in ends {
+                    next[i] += count;
+                }
+            }
+
+            readings = next;
+        }
+
+        readings[length]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::{test_util::word_list, EmbeddedList};
+
+    #[test]
+    fn detects_a_uniquely_decodable_list() {
+        // Every word has the same length.
+        assert!(word_list(&[]).is_uniquely_decodable(""));
+        assert_eq!(word_list(&[]).entropy_loss(4, "", 10), 0.0);
+    }
+
+    #[test]
+    fn detects_an_ambiguous_list() {
+        let word_list = word_list(&["in", "to", "into"]);
+
+        assert!(!word_list.is_uniquely_decodable(""));
+        assert!(word_list.is_uniquely_decodable(" "));
+    }
+
+    #[test]
+    fn detects_an_ambiguity_through_dangling_suffixes() {
+        // "a" + "bc" + "d" = "ab" + "cd", though no word is the concatenation
+        // of two others.
+        let word_list = word_list(&["a", "ab", "bc", "cd", "d"]);
+
+        assert!(!word_list.is_uniquely_decodable(""));
+    }
+
+    #[test]
+    fn detects_an_ambiguity_with_the_separator() {
+        let word_list = word_list(&["a", "a-b", "b"]);
+
+        assert!(!word_list.is_uniquely_decodable("-"));
+        assert!(word_list.is_uniquely_decodable(" "));
+    }
+
+    #[test]
+    fn counts_the_readings_of_a_passphrase() {
+        let word_list = word_list(&["in", "to", "into"]);
+
+        assert_eq!(word_list.count_readings("intointo", 3, "", 4), 2.0);
+        assert_eq!(word_list.count_readings("intointo", 4, "", 4), 1.0);
+        assert_eq!(word_list.count_readings("into to", 2, " ", 4), 1.0);
+    }
+
+    #[test]
+    fn checks_the_embedded_lists() {
+        for list in [EmbeddedList::EN, EmbeddedList::FR] {
+            let word_list = WordList::embedded(list).unwrap();
+
+            assert!(word_list.is_uniquely_decodable(" "));
+            assert!(!word_list.is_uniquely_decodable(""));
+        }
+    }
+}
@@ -0,0 +1,194 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Transparent decompression of word lists.
+//!
+//! Compressed files are detected by their magic bytes. Each format is
+//! supported only if its cargo feature is enabled: `gzip`, `zstd` or `xz`.
+
+use std::io::{self, BufRead, BufReader, Cursor, Read};
+
+/// The magic bytes of gzip files.
+const GZIP_MAGIC: &[u8] = b"\x1f\x8b";
+
+/// The magic bytes of Zstandard files.
+const ZSTD_MAGIC: &[u8] = b"\x28\xb5\x2f\xfd";
+
+/// The magic bytes of xz files.
+const XZ_MAGIC: &[u8] = b"\xfd7zXZ\x00";
+
+/// The length of the longest magic bytes.
+const MAGIC_LENGTH: usize = XZ_MAGIC.len();
+
+/// Opens a word list for reading, decompressing it if needed.
+pub fn open<'a>(
+    reader: impl Read + 'a,
+) -> io::Result<BufReader<Box<dyn Read + 'a>>> {
+    Ok(BufReader::new(decompress(reader)?))
+}
+
+/// Wraps a reader with a decompressor if its content is compressed.
+pub fn decompress<'a, R: Read + 'a>(
+    mut reader: R,
+) -> io::Result<Box<dyn Read + 'a>> {
+    // A single read can return fewer bytes than the magic bytes, for instance
+    // from a pipe, so read until there are enough of them or the end.
+    let mut magic = Vec::with_capacity(MAGIC_LENGTH);
+    reader
+        .by_ref()
+        .take(MAGIC_LENGTH as u64)
+        .read_to_end(&mut magic)?;
+
+    let stream = BufReader::new(Cursor::new(magic.clone()).chain(reader));
+
+    if magic.starts_with(GZIP_MAGIC) {
+        gzip(stream)
+    } else if magic.starts_with(ZSTD_MAGIC) {
+        zstd(stream)
+    } else if magic.starts_with(XZ_MAGIC) {
+        xz(stream)
+    } else {
+        Ok(Box::new(stream))
+    }
+}
+
+/// Decompresses a gzip stream.
+#[cfg(feature = "gzip")]
+fn gzip<'a, R: BufRead + 'a>(reader: R) -> io::Result<Box<dyn Read + 'a>> {
+    Ok(Box::new(flate2::bufread::MultiGzDecoder::new(reader)))
+}
+
+/// Decompresses a gzip stream.
+#[cfg(not(feature = "gzip"))]
+fn gzip<'a, R: BufRead + 'a>(_reader: R) -> io::Result<Box<dyn Read + 'a>> {
+    Err(unsupported("gzip"))
+}
+
+/// Decompresses a Zstandard stream.
+#[cfg(feature = "zstd")]
+fn zstd<'a, R: BufRead + 'a>(reader: R) -> io::Result<Box<dyn Read + 'a>> {
+    Ok(Box::new(zstd::stream::read::Decoder::with_buffer(reader)?))
+}
+
+/// Decompresses a Zstandard stream.
+#[cfg(not(feature = "zstd"))]
+fn zstd<'a, R: BufRead + 'a>(_reader: R) -> io::Result<Box<dyn Read + 'a>> {
+    Err(unsupported("zstd"))
+}
+
+/// Decompresses an xz stream.
+#[cfg(feature = "xz")]
+fn xz<'a, R: BufRead + 'a>(reader: R) -> io::Result<Box<dyn Read + 'a>> {
+    Ok(Box::new(xz2::bufread::XzDecoder::new_multi_decoder(reader)))
+}
+
+/// Decompresses an xz stream.
+#[cfg(not(feature = "xz"))]
+fn xz<'a, R: BufRead + 'a>(_reader: R) -> io::Result<Box<dyn Read + 'a>> {
+    Err(unsupported("xz"))
+}
+
+/// Builds an error for a compression format whose feature is disabled.
+#[cfg(not(all(feature = "gzip", feature = "zstd", feature = "xz")))]
+fn unsupported(feature: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!(
+            "{feature}-compressed file, \
+             but the `{feature}` feature is disabled"
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    const CONTENT: &[u8] = b"abbey\nabide\n";
+
+    /// A reader returning one byte at a time, like a slow pipe.
+    struct ByteByByte<'a>(&'a [u8]);
+
+    impl Read for ByteByByte<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match (self.0.split_first(), buf.first_mut()) {
+                (Some((&byte, rest)), Some(first)) => {
+                    *first = byte;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    fn decompressed(content: impl Read) -> io::Result<Vec<u8>> {
+        let mut decompressed = vec![];
+        decompress(content)?.read_to_end(&mut decompressed)?;
+        Ok(decompressed)
+    }
+
+    #[test]
+    fn reads_uncompressed_content() {
+        assert_eq!(decompressed(CONTENT).unwrap(), CONTENT);
+        assert_eq!(decompressed(&b""[..]).unwrap(), b"");
+        assert_eq!(decompressed(ByteByByte(CONTENT)).unwrap(), CONTENT);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn decompresses_gzip() {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(
+            vec![],
+            flate2::Compression::default(),
+        );
+        encoder.write_all(CONTENT).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert_eq!(decompressed(&compressed[..]).unwrap(), CONTENT);
+        assert_eq!(decompressed(ByteByByte(&compressed)).unwrap(), CONTENT);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn decompresses_zstd() {
+        let compressed = zstd::encode_all(CONTENT, 0).unwrap();
+        assert_eq!(decompressed(&compressed[..]).unwrap(), CONTENT);
+    }
+
+    #[cfg(feature = "xz")]
+    #[test]
+    fn decompresses_xz() {
+        use std::io::Write;
+
+        let mut encoder = xz2::write::XzEncoder::new(vec![], 6);
+        encoder.write_all(CONTENT).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert_eq!(decompressed(&compressed[..]).unwrap(), CONTENT);
+    }
+
+    #[cfg(not(feature = "gzip"))]
+    #[test]
+    fn refuses_gzip_without_the_feature() {
+        let err = decompressed(&b"\x1f\x8b\x08\x00"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
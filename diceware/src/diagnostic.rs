@@ -0,0 +1,267 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use alloc::string::String;
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Normalization;
+
+/// A problem found in a word list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Diagnostic {
+    /// The line where the problem has been found, starting at 1.
+    ///
+    /// For word lists that are not read from a file, this is the position of
+    /// the word in the list. Problems concerning the whole list, like its
+    /// length, have no line.
+    pub line: Option<usize>,
+
+    /// The kind of problem.
+    pub kind: DiagnosticKind,
+}
+
+/// The severity of a problem found in a word list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Severity {
+    /// The word list cannot be used.
+    Error,
+
+    /// The word list can be used, but some words can cause problems to users.
+    Warning,
+
+    /// Information about a change made to the word list when loading it.
+    Note,
+}
+
+/// The kinds of problems found in a word list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum DiagnosticKind {
+    /// The word list is not 7776-word long.
+    InvalidLength(usize),
+
+    /// The word has already been seen on another line.
+    DuplicateWord {
+        /// The duplicate word.
+        word: String,
+
+        /// The line where the word has been seen first.
+        first_line: usize,
+    },
+
+    /// The line is empty.
+    EmptyLine,
+
+    /// The word contains whitespace.
+    Whitespace(String),
+
+    /// The line is not valid UTF-8.
+    ///
+    /// Lists in other encodings, like Latin-1, can be read with
+    /// [`LoadOptions::encoding`](./struct.LoadOptions.html#method.encoding).
+    InvalidUtf8 {
+        /// The offset of the first invalid byte in the file.
+        offset: usize,
+    },
+
+    /// The dice rolls numbering the line do not match its position.
+    InvalidNumbering {
+        /// The expected dice rolls.
+        expected: String,
+
+        /// The dice rolls found on the line.
+        found: String,
+    },
+
+    /// The line is not numbered, while the first line of the list is.
+    MissingNumbering {
+        /// The expected dice rolls.
+        expected: String,
+    },
+
+    /// The word contains invisible characters, like control or zero-width
+    /// characters.
+    InvisibleChar(String),
+
+    /// The word contains non-ASCII characters, which can be hard to type on
+    /// some keyboards.
+    NonAscii(String),
+
+    /// The word contains uppercase characters.
+    Uppercase(String),
+
+    /// The word is not in the normalization form of the list, so it has been
+    /// normalized when loading the list.
+    NotNormalized {
+        /// The word, as written in the list.
+        word: String,
+
+        /// The normalization form of the list.
+        form: Normalization,
+    },
+
+    /// The word mixes characters from several scripts, like Latin and
+    /// Cyrillic.
+    MixedScript(String),
+
+    /// The word cannot be transliterated to ASCII.
+    NotFoldable(String),
+
+    /// The word has been transliterated to ASCII.
+    Folded {
+        /// The word, as written in the list.
+        word: String,
+
+        /// The transliterated word.
+        folded: String,
+    },
+
+    /// The word looks like another word from the list.
+    Confusable {
+        /// The word.
+        word: String,
+
+        /// The word it can be confused with.
+        other: String,
+
+        /// The line of the other word.
+        other_line: usize,
+    },
+}
+
+impl DiagnosticKind {
+    /// Returns the severity of the problem.
+    pub const fn severity(&self) -> Severity {
+        match self {
+            Self::InvalidLength(_)
+            | Self::DuplicateWord { .. }
+            | Self::EmptyLine
+            | Self::Whitespace(_)
+            | Self::InvalidUtf8 { .. }
+            | Self::InvalidNumbering { .. }
+            | Self::MissingNumbering { .. }
+            | Self::NotFoldable(_) => Severity::Error,
+
+            Self::InvisibleChar(_)
+            | Self::NonAscii(_)
+            | Self::Uppercase(_)
+            | Self::NotNormalized { .. }
+            | Self::MixedScript(_)
+            | Self::Confusable { .. } => Severity::Warning,
+
+            Self::Folded { .. } => Severity::Note,
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+            Self::Note => write!(f, "note"),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.kind),
+            None => self.kind.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength(length) => {
+                write!(f, "invalid length: {} words instead of 7776", length)
+            }
+
+            Self::DuplicateWord { word, first_line } => write!(
+                f,
+                "duplicate word {:?}, first seen on line {}",
+                word, first_line
+            ),
+
+            Self::EmptyLine => write!(f, "empty line"),
+            Self::Whitespace(word) => {
+                write!(f, "whitespace in word {:?}", word)
+            }
+            Self::InvalidUtf8 { offset } => {
+                write!(f, "invalid UTF-8 at byte offset {}", offset)
+            }
+
+            Self::InvalidNumbering { expected, found } => write!(
+                f,
+                "invalid numbering: expected {}, found {}",
+                expected, found
+            ),
+
+            Self::MissingNumbering { expected } => {
+                write!(f, "missing numbering: expected {}", expected)
+            }
+
+            Self::InvisibleChar(word) => {
+                write!(f, "invisible character in word {:?}", word)
+            }
+
+            Self::NonAscii(word) => write!(f, "non-ASCII word {:?}", word),
+            Self::Uppercase(word) => write!(f, "uppercase in word {:?}", word),
+
+            Self::NotNormalized { word, form } => {
+                write!(f, "word {:?} is not in {} form", word, form)
+            }
+
+            Self::MixedScript(word) => {
+                write!(f, "word {:?} mixes several scripts", word)
+            }
+
+            Self::NotFoldable(word) => {
+                write!(f, "word {:?} cannot be transliterated to ASCII", word)
+            }
+
+            Self::Folded { word, folded } => {
+                write!(f, "word {:?} transliterated to {:?}", word, folded)
+            }
+
+            Self::Confusable {
+                word,
+                other,
+                other_line,
+            } => write!(
+                f,
+                "word {:?} looks like {:?} on line {}",
+                word, other, other_line
+            ),
+        }
+    }
+}
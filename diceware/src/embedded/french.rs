@@ -2,7 +2,7 @@ pub const WORD_LIST: [&str; 7776] = [
     "===", "==", "=", "---", "--", "-", ";", ":-(", ":-)", ":(", ":)", ":",
     "!!!", "!!", "!", "???", "??", "?", "\"\"\"\"", "()", "(", ")", "@", "$$$",
     "$$", "$", "***", "**", "*", "&", "###", "##", "#", "%%%", "%%", "%",
-    "+++", "++", "+", "", "0", "1", "10", "100", "1000", "101", "11", "111",
+    "+++", "++", "+", "\"", "0", "1", "10", "100", "1000", "101", "11", "111",
     "1111", "12", "123", "1234", "13", "14", "1492", "15", "1500", "16",
     "1600", "17", "1700", "18", "1800", "19", "1900", "1910", "1920", "1925",
     "1930", "1935", "1940", "1945", "1950", "1955", "1960", "1965", "1970",
@@ -17,5 +17,8 @@
 pub use self::english::WORD_LIST as EN;
 pub use self::french::WORD_LIST as FR;
 
+// The lists are only used by reference, which is promoted to a static.
+#[allow(clippy::large_const_arrays)]
 mod english;
+#[allow(clippy::large_const_arrays)]
 mod french;
@@ -14,21 +14,70 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::{error, fmt, io, result};
+use alloc::{string::String, vec::Vec};
+use core::{error, fmt, result};
+
+#[cfg(all(feature = "serde", feature = "std"))]
+use serde::Serializer;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::Diagnostic;
 
 /// Short hand for the
 /// [`Result`](https://doc.rust-lang.org/std/result/enum.Result.html) type.
 pub type Result<T> = result::Result<T, Error>;
 
 /// Diceware errors.
+///
+/// With the `serde` feature, errors can be serialized, IO errors being
+/// serialized as their message.
+///
+/// Paths are stored as strings, so that [`Error::WordList`] has the same
+/// fields with and without the `std` feature. Enabling `std` only adds the
+/// [`Error::IO`] variant, which wildcard arms already cover.
 #[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize),
+    serde(rename_all = "snake_case")
+)]
+#[non_exhaustive]
 pub enum Error {
     /// IO errors, typically encountered when trying to read a word list from a
     /// file.
-    IO(io::Error),
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "serde", serde(rename = "io"))]
+    IO {
+        /// The path of the file, if the list has been read from a file.
+        path: Option<String>,
+
+        /// The IO error.
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_io"))]
+        error: io::Error,
+    },
 
     /// Word list errors, encountered when the word list is invalid.
-    WordList(WordListError),
+    WordList {
+        /// The path of the word list file, if the list has been read from a
+        /// file.
+        path: Option<String>,
+
+        /// The word list error.
+        ///
+        /// When the list has been read from a file, its diagnostics contain
+        /// the line of each problem.
+        error: WordListError,
+    },
+
+    /// Lookup errors, encountered when a passphrase cannot be looked up in a
+    /// word list.
+    Lookup(LookupError),
+
+    /// Mnemonic errors, encountered when words cannot be decoded to data.
+    Mnemonic(MnemonicError),
 
     /// Error for when the number of words to generate is 0.
     NoWords,
@@ -36,65 +85,241 @@ pub enum Error {
 
 /// Word list errors.
 #[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum WordListError {
-    /// Error for when the word list is not 7776-word long.
+    /// Error for when the word list is invalid, with every problem found.
+    Invalid(Vec<Diagnostic>),
+
+    /// Error for when passphrases made of words joined with a separator can be
+    /// read in several ways.
+    Ambiguous {
+        /// The separator.
+        separator: String,
+    },
+}
+
+/// Lookup errors.
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum LookupError {
+    /// Error for when a word is not in the list, even after removing a special
+    /// character.
+    UnknownWord(String),
+
+    /// Error for when removing a special character from a word can give
+    /// several words from the list.
+    AmbiguousWord(String),
+}
+
+/// Mnemonic errors.
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum MnemonicError {
+    /// Error for when there are no words to decode.
+    Empty,
+
+    /// Error for when no data length can be encoded with this number of words.
     InvalidLength(usize),
 
-    /// Error for when the word list contains duplicates.
-    DuplicateWord(String),
+    /// Error for when the words encode a value too large for the data length.
+    InvalidPadding,
+
+    /// Error for when the checksum word does not match the data.
+    InvalidChecksum,
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::IO(err) => err.fmt(f),
-            Self::WordList(err) => err.fmt(f),
+            #[cfg(feature = "std")]
+            Self::IO {
+                path: Some(path),
+                error,
+            } => write!(f, "{}: {}", path, error),
+
+            #[cfg(feature = "std")]
+            Self::IO { path: None, error } => write!(f, "IO: {}", error),
+
+            Self::WordList {
+                path: Some(path),
+                error,
+            } => write!(f, "{}: {}", path, error),
+
+            Self::WordList { error, .. } => error.fmt(f),
+            Self::Lookup(err) => err.fmt(f),
+            Self::Mnemonic(err) => err.fmt(f),
             Self::NoWords => write!(f, "No words to generate"),
         }
     }
 }
 
 impl error::Error for Error {
-    fn cause(&self) -> Option<&dyn error::Error> {
+    // The message of the inner error is already part of the message, so the
+    // chain continues with its source, to be printed only once by reporters.
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
-            Self::IO(err) => Some(err),
-            Self::WordList(err) => Some(err),
+            #[cfg(feature = "std")]
+            Self::IO { error, .. } => error.source(),
+            Self::WordList { error, .. } => error.source(),
+            Self::Lookup(err) => err.source(),
+            Self::Mnemonic(err) => err.source(),
             Self::NoWords => None,
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl From<io::Error> for Error {
-    fn from(err: io::Error) -> Self {
-        Self::IO(err)
+    fn from(error: io::Error) -> Self {
+        Self::IO { path: None, error }
     }
 }
 
 impl From<WordListError> for Error {
-    fn from(err: WordListError) -> Self {
-        Self::WordList(err)
+    fn from(error: WordListError) -> Self {
+        Self::WordList { path: None, error }
+    }
+}
+
+impl From<LookupError> for Error {
+    fn from(err: LookupError) -> Self {
+        Self::Lookup(err)
+    }
+}
+
+impl From<MnemonicError> for Error {
+    fn from(err: MnemonicError) -> Self {
+        Self::Mnemonic(err)
     }
 }
 
 impl fmt::Display for WordListError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::InvalidLength(length) => {
-                write!(f, "Word list: invalid length ({})", length)
+            Self::Invalid(diagnostics) => match &diagnostics[..] {
+                [diagnostic] => write!(f, "Word list: {}", diagnostic),
+                _ => {
+                    write!(f, "Word list: {} problems found", diagnostics.len())
+                }
+            },
+
+            Self::Ambiguous { separator } => write!(
+                f,
+                "Word list: words joined with {:?} can be read in several ways",
+                separator
+            ),
+        }
+    }
+}
+
+impl error::Error for WordListError {}
+
+impl fmt::Display for LookupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownWord(word) => {
+                write!(f, "Lookup: {}: unknown word", word)
             }
 
-            Self::DuplicateWord(word) => {
-                write!(f, "Word list: {}: duplicate word", word)
+            Self::AmbiguousWord(word) => {
+                write!(f, "Lookup: {}: ambiguous word", word)
             }
         }
     }
 }
 
-impl error::Error for WordListError {
-    fn description(&self) -> &str {
+impl error::Error for LookupError {}
+
+impl fmt::Display for MnemonicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::InvalidLength(_) => "Invalid word list length",
-            Self::DuplicateWord(_) => "Duplicate word in the list",
+            Self::Empty => write!(f, "Mnemonic: no words"),
+
+            Self::InvalidLength(words) => {
+                write!(f, "Mnemonic: invalid number of words ({})", words)
+            }
+
+            Self::InvalidPadding => write!(f, "Mnemonic: invalid padding"),
+            Self::InvalidChecksum => write!(f, "Mnemonic: invalid checksum"),
         }
     }
 }
+
+impl error::Error for MnemonicError {}
+
+/// Serializes an IO error as its message.
+#[cfg(all(feature = "serde", feature = "std"))]
+fn serialize_io<S: Serializer>(
+    error: &io::Error,
+    serializer: S,
+) -> result::Result<S::Ok, S::Error> {
+    serializer.collect_str(error)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    #[cfg(feature = "serde")]
+    use crate::DiagnosticKind;
+
+    #[test]
+    fn does_not_repeat_the_inner_error_in_the_chain() {
+        let err = Error::from(LookupError::UnknownWord(String::from("foo")));
+
+        assert_eq!(err.to_string(), "Lookup: foo: unknown word");
+        assert!(error::Error::source(&err).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serializes_errors() {
+        let err = Error::WordList {
+            path: Some(String::from("words.txt")),
+            error: WordListError::Invalid(vec![Diagnostic {
+                line: Some(2),
+                kind: DiagnosticKind::EmptyLine,
+            }]),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            serde_json::json!({
+                "word_list": {
+                    "path": "words.txt",
+                    "error": {
+                        "invalid": [{ "line": 2, "kind": "empty_line" }],
+                    },
+                },
+            })
+        );
+
+        let err = Error::IO {
+            path: None,
+            error: io::Error::new(io::ErrorKind::NotFound, "not found"),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            serde_json::json!({ "io": { "path": null, "error": "not found" } })
+        );
+
+        assert_eq!(
+            serde_json::to_value(Error::NoWords).unwrap(),
+            serde_json::json!("no_words")
+        );
+    }
+}
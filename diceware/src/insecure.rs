@@ -0,0 +1,72 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! **Insecure** deterministic passphrase generation.
+//!
+//! The functions in this module generate passphrases from an explicit seed:
+//! the same seed, configuration and word list always give the same
+//! passphrase. This is useful to write reproducible tests and documentation
+//! examples, but anyone knowing the seed can regenerate the passphrase.
+//!
+//! **Never use this module to generate real passphrases.**
+
+use alloc::string::String;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+use crate::{Config, Passphrases, Result};
+
+/// Creates a deterministic random number generator from a seed.
+///
+/// **Never use it to generate real passphrases.**
+pub fn seeded_rng(seed: u64) -> ChaCha20Rng {
+    ChaCha20Rng::seed_from_u64(seed)
+}
+
+/// Makes a passphrase given a [`config`](../struct.Config.html) and a seed.
+///
+/// **Never use it to generate real passphrases.**
+///
+/// # Example
+///
+/// ```rust
+/// use diceware::{insecure, Config, EmbeddedList};
+///
+/// let config = Config::with_embedded(EmbeddedList::EN, 6, false);
+/// let passphrase = insecure::make_passphrase(config, 42).unwrap();
+///
+/// let config = Config::with_embedded(EmbeddedList::EN, 6, false);
+/// assert_eq!(insecure::make_passphrase(config, 42).unwrap(), passphrase);
+/// ```
+pub fn make_passphrase(config: Config<'_>, seed: u64) -> Result<String> {
+    let mut passphrases = passphrases(config, seed)?;
+
+    // NOTE(unwrap): Passphrases is an infinite iterator.
+    #[allow(clippy::unwrap_used)]
+    Ok(passphrases.next().unwrap())
+}
+
+/// Makes an infinite iterator over passphrases given a
+/// [`config`](../struct.Config.html) and a seed.
+///
+/// **Never use it to generate real passphrases.**
+pub fn passphrases(
+    config: Config<'_>,
+    seed: u64,
+) -> Result<Passphrases<ChaCha20Rng>> {
+    Passphrases::new(config, seeded_rng(seed))
+}
@@ -15,6 +15,9 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 #![doc = include_str!("../../README.md")]
+// The README indents the continuation of list items by four spaces.
+#![allow(clippy::doc_overindented_list_items)]
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(rust_2018_idioms)]
 #![warn(clippy::redundant_pub_crate)]
 #![warn(clippy::unwrap_used)]
@@ -23,17 +26,62 @@
 #![deny(unused_must_use)]
 #![forbid(unsafe_code)]
 
+extern crate alloc;
+
+#[cfg(any(test, feature = "insecure-seed"))]
+pub mod insecure;
+pub mod mnemonic;
+
+mod ambiguity;
+#[cfg(feature = "std")]
+mod compression;
+mod diagnostic;
 mod embedded;
 mod error;
+mod load;
+mod owned;
+mod passphrase;
+mod stats;
+#[cfg(test)]
+mod test_util;
+mod word_list;
 
+pub use self::diagnostic::{Diagnostic, DiagnosticKind, Severity};
 pub use self::error::*;
-
-use std::{collections::HashSet, fs, path::Path};
-
-use rand::{prelude::*, rngs::OsRng};
+pub use self::load::{Encoding, LoadOptions, Normalization};
+pub use self::owned::{ListSource, OwnedConfig};
+pub use self::passphrase::{InsertedSpecialChar, Passphrase};
+pub use self::stats::{Stats, PREFIX_LENGTH};
+pub use self::word_list::{
+    check_bytes, dice_rolls, SpecialChar, WordList, WordLookup,
+    WORD_LIST_LENGTH,
+};
+
+#[cfg(feature = "std")]
+pub use self::word_list::{check_file, check_file_with_options, check_reader};
+
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+#[cfg(not(feature = "std"))]
+use core::{convert::Infallible, marker::PhantomData};
+#[cfg(feature = "std")]
+use std::io::Read;
+
+use rand::prelude::*;
+#[cfg(feature = "std")]
+use rand::rngs::OsRng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use unicode_segmentation::UnicodeSegmentation;
 
-use self::error::WordListError::*;
+/// The special characters that can be inserted in a passphrase.
+const SPECIAL_CHARS: &str = "~!#$%^&*()-=+[]\\{}:;\"'<>?/0123456789";
 
 /// Configuration for the passphrase generator.
 ///
@@ -41,20 +89,39 @@ use self::error::WordListError::*;
 ///
 /// * [`Config::with_filename`](#method.with_filename)
 /// * [`Config::with_embedded`](#method.with_embedded)
+/// * [`Config::with_reader`](#method.with_reader)
+/// * [`Config::with_word_list`](#method.with_word_list)
 pub struct Config<'a> {
-    word_list: WordList<'a>,
+    word_list: Source<'a>,
     words: usize,
     with_special_char: bool,
+    checksum_word: bool,
+    separator: &'a str,
+    load_options: LoadOptions,
 }
 
-/// A word list.
-enum WordList<'a> {
+/// A word list source.
+enum Source<'a> {
+    #[cfg(feature = "std")]
     File(&'a str),
     Embedded(EmbeddedList),
+    List(WordList),
+    #[cfg(feature = "std")]
+    Reader(Box<dyn Read + Send + 'a>),
+    /// Uninhabited variant using the lifetime when the `std` sources are
+    /// disabled.
+    #[cfg(not(feature = "std"))]
+    #[allow(dead_code)]
+    Phantom(Infallible, PhantomData<&'a ()>),
 }
 
 /// The list of embedded word lists.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum EmbeddedList {
     /// The original English Diceware word list.
     EN,
@@ -63,10 +130,17 @@ pub enum EmbeddedList {
     /// French word list.
     ///
     /// To avoid encoding or accessibility problems, `Église` has been replaced
-    /// by `Eglise` in the list.
+    /// by `Eglise` in the list. Other lists can be transliterated to ASCII the
+    /// same way with
+    /// [`LoadOptions::ascii_folding`](./struct.LoadOptions.html#method.ascii_folding).
+    ///
+    /// The original list also contains an empty word, its 40th one, which has
+    /// been replaced by `"`: this changes the fingerprint of the list, and
+    /// passphrases or mnemonics made with the empty word now use `"` instead.
     FR,
 }
 
+#[allow(clippy::use_self)]
 impl<'a> Config<'a> {
     /// Creates a configuration using an external word list.
     ///
@@ -79,15 +153,19 @@ impl<'a> Config<'a> {
     /// // using the word list in words.txt:
     /// let config = Config::with_filename("words.txt", 8, true);
     /// ```
+    #[cfg(feature = "std")]
     pub fn with_filename(
         filename: &'a str,
         words: usize,
         with_special_char: bool,
     ) -> Config<'a> {
         Config {
-            word_list: WordList::File(filename),
+            word_list: Source::File(filename),
             words,
             with_special_char,
+            checksum_word: false,
+            separator: " ",
+            load_options: LoadOptions::default(),
         }
     }
 
@@ -108,33 +186,369 @@ impl<'a> Config<'a> {
         with_special_char: bool,
     ) -> Config<'a> {
         Config {
-            word_list: WordList::Embedded(list),
+            word_list: Source::Embedded(list),
+            words,
+            with_special_char,
+            checksum_word: false,
+            separator: " ",
+            load_options: LoadOptions::default(),
+        }
+    }
+
+    /// Creates a configuration using a word list read from a reader, like the
+    /// standard input.
+    ///
+    /// The content is read in the same format as a word list file, when the
+    /// passphrase generator is created. See
+    /// [`WordList::from_file`](./struct.WordList.html#method.from_file).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::Config;
+    ///
+    /// // Create a configuration to generate 6 words without a special char,
+    /// // using a word list read from the standard input:
+    /// let config = Config::with_reader(std::io::stdin(), 6, false);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn with_reader(
+        reader: impl Read + Send + 'a,
+        words: usize,
+        with_special_char: bool,
+    ) -> Config<'a> {
+        Config {
+            word_list: Source::Reader(Box::new(reader)),
             words,
             with_special_char,
+            checksum_word: false,
+            separator: " ",
+            load_options: LoadOptions::default(),
         }
     }
+
+    /// Creates a configuration using an already loaded word list.
+    ///
+    /// The list has been checked when it was created, with its own load
+    /// options: the options set with
+    /// [`Config::load_options`](#method.load_options) are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::{Config, EmbeddedList, WordList};
+    ///
+    /// let word_list = WordList::embedded(EmbeddedList::EN).unwrap();
+    /// let config = Config::with_word_list(word_list, 6, false);
+    /// ```
+    pub fn with_word_list(
+        word_list: WordList,
+        words: usize,
+        with_special_char: bool,
+    ) -> Config<'a> {
+        Config {
+            word_list: Source::List(word_list),
+            words,
+            with_special_char,
+            checksum_word: false,
+            separator: " ",
+            load_options: LoadOptions::default(),
+        }
+    }
+
+    /// Appends a checksum word to the generated passphrases.
+    ///
+    /// The checksum word is derived from the indices of the other words in the
+    /// list. It enables to detect typos with
+    /// [`WordList::verify_checksum`](./struct.WordList.html#method.verify_checksum).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::{Config, EmbeddedList};
+    ///
+    /// // Create a configuration to generate 6 words followed by a checksum
+    /// // word, using the embedded English word list:
+    /// let config =
+    ///     Config::with_embedded(EmbeddedList::EN, 6, false).checksum_word(true);
+    /// ```
+    pub fn checksum_word(mut self, checksum_word: bool) -> Self {
+        self.checksum_word = checksum_word;
+        self
+    }
+
+    /// Sets the separator between words, which is a space by default.
+    ///
+    /// Passphrases are refused if they could be read in several ways with the
+    /// chosen separator, like `in` + `to` and `into` without a separator. See
+    /// [`WordList::is_uniquely_decodable`](./struct.WordList.html#method.is_uniquely_decodable).
+    /// With a special character, separators containing one of the special
+    /// characters are refused too, as the inserted character could be read as
+    /// a separator.
+    ///
+    /// Checksum verification and lookup expect words separated by spaces.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::{Config, EmbeddedList, Error, WordListError};
+    ///
+    /// let config =
+    ///     Config::with_embedded(EmbeddedList::EN, 6, false).separator("-");
+    /// let passphrase = diceware::make_passphrase(config).unwrap();
+    /// assert_eq!(passphrase.split('-').count(), 6);
+    ///
+    /// // The English list is ambiguous without a separator.
+    /// let config =
+    ///     Config::with_embedded(EmbeddedList::EN, 6, false).separator("");
+    /// assert!(matches!(
+    ///     diceware::make_passphrase(config),
+    ///     Err(Error::WordList {
+    ///         error: WordListError::Ambiguous { .. },
+    ///         ..
+    ///     })
+    /// ));
+    /// ```
+    pub fn separator(mut self, separator: &'a str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Sets the options used to load the word list.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::{Config, LoadOptions, Normalization};
+    ///
+    /// let options = LoadOptions::default().normalization(Normalization::NFKC);
+    /// let config =
+    ///     Config::with_filename("words.txt", 8, false).load_options(options);
+    /// ```
+    pub fn load_options(mut self, load_options: LoadOptions) -> Self {
+        self.load_options = load_options;
+        self
+    }
+
+    /// Returns the entropy of the passphrases generated with this
+    /// configuration, in bits.
+    ///
+    /// See [`Passphrases::entropy`](./struct.Passphrases.html#method.entropy)
+    /// for details.
+    pub fn entropy(&self) -> f64 {
+        entropy(self.words, self.with_special_char)
+    }
 }
 
-impl<'a> WordList<'a> {
-    /// Gets the word list as a vector of strings.
-    fn get(&self) -> Result<Vec<String>> {
-        let word_list = match self {
-            WordList::File(filename) => get_wordlist(filename)?,
-            WordList::Embedded(list) => get_embedded_list(list),
-        };
+impl<'a> Source<'a> {
+    /// Gets the word list.
+    fn get(self, options: &LoadOptions) -> Result<WordList> {
+        match self {
+            #[cfg(feature = "std")]
+            Source::File(filename) => {
+                WordList::from_file_with_options(filename, options)
+            }
 
-        // This block limits the scope of the &word_list borrow.
-        {
-            // Check the list for duplicates.
-            let mut hash_list = HashSet::<&str>::new();
-            for word in &word_list {
-                if !hash_list.insert(word) {
-                    return Err(Error::WordList(DuplicateWord(word.clone())));
-                }
+            Source::Embedded(list) => {
+                WordList::embedded_with_options(list, options)
             }
+
+            Source::List(word_list) => Ok(word_list),
+
+            #[cfg(feature = "std")]
+            Source::Reader(reader) => WordList::from_reader(reader, options),
+
+            #[cfg(not(feature = "std"))]
+            Source::Phantom(never, _) => match never {},
+        }
+    }
+
+    /// Returns the path of the word list file, if any.
+    fn path(&self) -> Option<String> {
+        match self {
+            #[cfg(feature = "std")]
+            Source::File(filename) => Some(String::from(*filename)),
+            _ => None,
+        }
+    }
+}
+
+/// An infinite iterator over passphrases.
+///
+/// The word list is loaded and checked only once, when the iterator is
+/// created. Each call to [`next`](#method.next) then generates a new passphrase
+/// using the random number generator given to
+/// [`Passphrases::new`](#method.new).
+///
+/// # Example
+///
+/// ```rust
+/// use diceware::{Config, EmbeddedList};
+///
+/// // Make three 6-word passphrases from the embedded English list.
+/// let config = Config::with_embedded(EmbeddedList::EN, 6, false);
+/// let passphrases: Vec<String> =
+///     diceware::passphrases(config).unwrap().take(3).collect();
+/// ```
+pub struct Passphrases<R> {
+    word_list: WordList,
+    words: usize,
+    with_special_char: bool,
+    checksum_word: bool,
+    separator: String,
+    rng: R,
+}
+
+impl<R: Rng> Passphrases<R> {
+    /// Creates a passphrase iterator given a [`config`](./struct.Config.html)
+    /// and a random number generator.
+    ///
+    /// This is the way to generate passphrases without the `std` feature, for
+    /// instance with a hardware random number generator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::{Config, EmbeddedList, Passphrases};
+    /// use rand::rngs::OsRng;
+    ///
+    /// let config = Config::with_embedded(EmbeddedList::FR, 8, true);
+    /// let mut passphrases = Passphrases::new(config, OsRng).unwrap();
+    /// let passphrase = passphrases.next().unwrap();
+    /// ```
+    pub fn new(config: Config<'_>, rng: R) -> Result<Self> {
+        if config.words < 1 {
+            return Err(Error::NoWords);
+        }
+
+        let path = config.word_list.path();
+        let word_list = config.word_list.get(&config.load_options)?;
+
+        // An inserted special character could be read as the separator.
+        let special_char_in_separator = config.with_special_char
+            && config.separator.contains(|c| SPECIAL_CHARS.contains(c));
+
+        if special_char_in_separator
+            || !word_list.is_uniquely_decodable(config.separator)
+        {
+            return Err(Error::WordList {
+                path,
+                error: WordListError::Ambiguous {
+                    separator: config.separator.to_owned(),
+                },
+            });
+        }
+
+        Ok(Self {
+            word_list,
+            words: config.words,
+            with_special_char: config.with_special_char,
+            checksum_word: config.checksum_word,
+            separator: config.separator.to_owned(),
+            rng,
+        })
+    }
+
+    /// Returns the word list used to generate the passphrases.
+    pub fn word_list(&self) -> &WordList {
+        &self.word_list
+    }
+
+    /// Returns the entropy of the generated passphrases, in bits.
+    ///
+    /// Each word adds `log2(7776)` bits of entropy. The special character, if
+    /// any, adds `log2(36)` bits for its value and `log2(words)` bits for the
+    /// word it is inserted in. Its position in the word is not accounted for,
+    /// as it depends on the word length. The checksum word does not add any
+    /// entropy, as it is derived from the other words.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::{Config, EmbeddedList};
+    ///
+    /// let config = Config::with_embedded(EmbeddedList::EN, 6, false);
+    /// let passphrases = diceware::passphrases(config).unwrap();
+    /// assert_eq!(passphrases.entropy().floor(), 77.0);
+    /// ```
+    pub fn entropy(&self) -> f64 {
+        entropy(self.words, self.with_special_char)
+    }
+
+    /// Generates a passphrase, with its structure.
+    ///
+    /// This generates the same passphrase as [`next`](#method.next) would, but
+    /// describes its words, special character and checksum word.
+    pub fn next_passphrase(&mut self) -> Passphrase {
+        let mut words: Vec<String> = (0..self.words)
+            .map(|_| {
+                // NOTE(unwrap): word_list cannot be empty.
+                #[allow(clippy::unwrap_used)]
+                self.word_list.words().choose(&mut self.rng).unwrap()
+            })
+            .cloned()
+            .collect();
+
+        let checksum_word = if self.checksum_word {
+            let indices: Vec<usize> = words
+                .iter()
+                .map(|word| {
+                    // NOTE(unwrap): words have been chosen from the list.
+                    #[allow(clippy::unwrap_used)]
+                    self.word_list.index_of(word).unwrap()
+                })
+                .collect();
+
+            Some(self.word_list.checksum_word(&indices).to_owned())
+        } else {
+            None
+        };
+
+        let mut special_char = None;
+
+        if self.with_special_char {
+            let chars: Vec<char> = SPECIAL_CHARS.chars().collect();
+
+            // NOTE(unwrap): chars is defined above and not empty.
+            #[allow(clippy::unwrap_used)]
+            let c = chars.choose(&mut self.rng).unwrap();
+
+            let word_idx = self.rng.gen_range(0..words.len());
+            let word = &mut words[word_idx];
+
+            let indices: Vec<usize> =
+                word.grapheme_indices(true).map(|(i, _)| i).collect();
+
+            // Word lists cannot contain empty words, but do not rely on it.
+            let idx = indices.choose(&mut self.rng).copied().unwrap_or(0);
+
+            word.insert(idx, *c);
+            special_char = Some(InsertedSpecialChar {
+                word: word_idx,
+                character: *c,
+                position: idx,
+            });
+        }
+
+        Passphrase {
+            words,
+            special_char,
+            checksum_word,
+            separator: self.separator.clone(),
+            entropy: self.entropy(),
         }
+    }
+}
 
-        Ok(word_list)
+impl<R: Rng> Iterator for Passphrases<R> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_passphrase().to_string())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
     }
 }
 
@@ -156,104 +570,69 @@ impl<'a> WordList<'a> {
 /// ```rust
 /// use diceware::{Config, EmbeddedList, Error};
 ///
-/// let filename = "words.txt";
-/// let config = Config::with_filename(filename, 8, false);
+/// let config = Config::with_filename("words.txt", 8, false);
 /// match diceware::make_passphrase(config) {
 ///     Ok(passphrase) => println!("{}", passphrase),
 ///
 ///     Err(err) => {
 ///         match err {
-///             // IO errors can occur when using an external word list.
-///             Error::IO(e) => eprintln!("Error: {}: {}", filename, e),
+///             // IO errors can occur when using an external word list. The
+///             // error message contains the path of the file.
+///             Error::IO { .. } => eprintln!("Error: {}", err),
 ///
 ///             // Word list errors can occur if the word list is invalid, i.e.
 ///             // its length is different than 7776 words or it contains
-///             // duplicates.
-///             Error::WordList(e) => eprintln!("Error: {}", e),
+///             // duplicates, or if it is ambiguous with the separator.
+///             Error::WordList { .. } => eprintln!("Error: {}", err),
 ///
 ///             // No words errors can occur if the number of words to generate
 ///             // is 0.
 ///             Error::NoWords => eprintln!("Error: {}", err),
+///
+///             // Other errors cannot occur when making a passphrase.
+///             _ => eprintln!("Error: {}", err),
 ///         }
 ///     }
 /// };
 /// ```
+#[cfg(feature = "std")]
 pub fn make_passphrase(config: Config<'_>) -> Result<String> {
-    if config.words < 1 {
-        return Err(Error::NoWords);
-    }
-
-    let mut rng = OsRng;
-
-    // We need to declare this mutable string before `word_list` if we want to
-    // use it to replace a word with its version containing a special character.
-    let mut word = String::new();
-
-    let word_list = config.word_list.get()?;
-    let mut words: Vec<&str> = (0..config.words)
-        .map(|_| {
-            // NOTE(unwrap): word_list cannot be empty.
-            #[allow(clippy::unwrap_used)]
-            word_list.choose(&mut rng).unwrap()
-        })
-        .map(AsRef::as_ref)
-        .collect();
-
-    if config.with_special_char {
-        let chars: Vec<char> =
-            "~!#$%^&*()-=+[]\\{}:;\"'<>?/0123456789".chars().collect();
-
-        // NOTE(unwrap): chars is defined above and not empty.
-        #[allow(clippy::unwrap_used)]
-        let c = chars.choose(&mut rng).unwrap();
-
-        let word_idx = rng.gen_range(0..words.len());
-        word.push_str(words[word_idx]);
-
-        let indices: Vec<usize> =
-            word.grapheme_indices(true).map(|(i, _)| i).collect();
-
-        // NOTE(unwrap): As word containts at least one character, there will be
-        // at least one character indice in indices.
-        #[allow(clippy::unwrap_used)]
-        let idx = indices.choose(&mut rng).unwrap();
-
-        word.insert(*idx, *c);
-        words[word_idx] = &word;
-    }
-
-    let passphrase = words.join(" ");
-
-    Ok(passphrase)
+    Ok(passphrases(config)?.next_passphrase().to_string())
 }
 
-/// Gets the word list from a file.
-fn get_wordlist(filename: impl AsRef<Path>) -> Result<Vec<String>> {
-    let content = fs::read_to_string(filename)?;
-
-    let length = content.lines().count();
-    if length != 7776 {
-        return Err(Error::WordList(InvalidLength(length)));
-    }
-
-    let word_list = content.lines().map(String::from).collect();
-    Ok(word_list)
+/// Makes an infinite iterator over passphrases given a
+/// [`config`](./struct.Config.html), using the operating system random number
+/// generator.
+///
+/// This is useful to generate several passphrases from the same configuration
+/// without loading and checking the word list each time.
+///
+/// # Example
+///
+/// ```rust
+/// use diceware::{Config, EmbeddedList};
+///
+/// let config = Config::with_embedded(EmbeddedList::EN, 8, false);
+/// for passphrase in diceware::passphrases(config).unwrap().take(5) {
+///     println!("{}", passphrase);
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub fn passphrases(config: Config<'_>) -> Result<Passphrases<OsRng>> {
+    Passphrases::new(config, OsRng)
 }
 
-/// Gets an embedded word list.
-fn get_embedded_list(list: &EmbeddedList) -> Vec<String> {
-    embedded_list(list)
-        .iter()
-        .map(|&w| String::from(w))
-        .collect()
-}
+/// Computes the entropy of a passphrase, in bits.
+fn entropy(words: usize, with_special_char: bool) -> f64 {
+    let words = words as f64;
+    let mut entropy = words * libm::log2(WORD_LIST_LENGTH as f64);
 
-/// Gets the corresponding embedded word list.
-fn embedded_list(list: &EmbeddedList) -> &[&str; 7776] {
-    match list {
-        EmbeddedList::EN => &embedded::EN,
-        EmbeddedList::FR => &embedded::FR,
+    if with_special_char {
+        entropy += libm::log2(SPECIAL_CHARS.chars().count() as f64);
+        entropy += libm::log2(words);
     }
+
+    entropy
 }
 
 #[cfg(test)]
@@ -261,13 +640,12 @@ mod tests {
     #![allow(clippy::unwrap_used)]
 
     use super::*;
+    use crate::{
+        test_util::{arb_list, letter_list},
+        word_list::embedded_list,
+    };
     use proptest::prelude::*;
 
-    /// Arbitrary embedded word list generator.
-    fn arb_list() -> BoxedStrategy<EmbeddedList> {
-        prop_oneof![Just(EmbeddedList::EN), Just(EmbeddedList::FR)].boxed()
-    }
-
     #[test]
     fn returns_an_error_if_number_of_words_is_zero() {
         let config = Config::with_embedded(EmbeddedList::FR, 0, false);
@@ -277,6 +655,40 @@ mod tests {
         assert_eq!(result.unwrap_err().to_string(), "No words to generate");
     }
 
+    #[test]
+    fn returns_an_error_if_number_of_words_is_zero_for_passphrases() {
+        let config = Config::with_embedded(EmbeddedList::EN, 0, false);
+        let result = passphrases(config);
+
+        assert!(result.is_err());
+        assert_eq!(result.err().unwrap().to_string(), "No words to generate");
+    }
+
+    proptest! {
+        #[test]
+        fn makes_passphrases(
+            ref list in arb_list(),
+            n in 1..20usize,
+            count in 1..10usize
+        ) {
+            let word_list = embedded_list(list);
+
+            let config = Config::with_embedded(list.clone(), n, false);
+            let passphrases: Vec<String> =
+                passphrases(config).unwrap().take(count).collect();
+
+            prop_assert_eq!(passphrases.len(), count);
+            for passphrase in passphrases {
+                prop_assert_eq!(passphrase.split(' ').count(), n);
+                prop_assert!(
+                    passphrase
+                        .split_whitespace()
+                        .all(|w| word_list.contains(&w))
+                );
+            }
+        }
+    }
+
     proptest! {
         #[test]
         fn makes_a_passphrase(ref list in arb_list(), n in 1..50usize) {
@@ -297,13 +709,12 @@ mod tests {
 
     proptest! {
         #[test]
-        fn makes_a_passphrase_with_special_char(
-            ref list in arb_list(),
-            n in 1..50usize
-        ) {
-            let word_list = embedded_list(list);
+        fn makes_a_passphrase_with_special_char(n in 1..50usize) {
+            // As the list has no special character, the word containing the
+            // inserted one cannot be another word of the list.
+            let word_list = letter_list();
 
-            let config = Config::with_embedded(list.clone(), n, true);
+            let config = Config::with_word_list(word_list.clone(), n, true);
             let result = make_passphrase(config);
 
             prop_assert!(result.is_ok());
@@ -311,7 +722,7 @@ mod tests {
             let passphrase = result.unwrap();
             let not_in_wordlist: Vec<&str> = passphrase
                 .split_whitespace()
-                .filter(|w| !word_list.contains(w))
+                .filter(|w| word_list.index_of(w).is_none())
                 .collect();
 
             prop_assert_eq!(not_in_wordlist.len(), 1);
@@ -326,11 +737,204 @@ mod tests {
                     let mut word = word_with_char.to_owned();
                     word.remove(i);
 
-                    word_list.contains(&word.as_ref())
+                    word_list.index_of(&word).is_some()
                 } else {
                     false
                 }
             }));
         }
     }
+
+    proptest! {
+        #[test]
+        fn inserts_the_special_char_in_a_single_word(
+            ref list in arb_list(),
+            n in 1..50usize,
+            seed: u64
+        ) {
+            // Words are chosen before inserting the special character, so
+            // using the same seed gives the same words.
+            let config = Config::with_embedded(list.clone(), n, false);
+            let without_char = insecure::make_passphrase(config, seed);
+
+            let config = Config::with_embedded(list.clone(), n, true);
+            let with_char = insecure::make_passphrase(config, seed);
+
+            prop_assert!(with_char.is_ok());
+
+            let without_char = without_char.unwrap();
+            let with_char = with_char.unwrap();
+            let changed: Vec<(&str, &str)> = without_char
+                .split(' ')
+                .zip(with_char.split(' '))
+                .filter(|(original, word)| original != word)
+                .collect();
+
+            prop_assert_eq!(changed.len(), 1);
+
+            let (original, word_with_char) = changed[0];
+            let chars: Vec<char> = "~!#$%^&*()-=+[]\\{}:;\"'<>?/0123456789"
+                .chars()
+                .collect();
+
+            let has_special_char = word_with_char.char_indices().any(|(i, c)| {
+                if chars.contains(&c) {
+                    let mut word = word_with_char.to_owned();
+                    word.remove(i);
+
+                    word == original
+                } else {
+                    false
+                }
+            });
+
+            prop_assert!(has_special_char);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn makes_a_passphrase_with_checksum_word(
+            ref list in arb_list(),
+            n in 1..20usize,
+            with_special_char: bool,
+            seed: u64
+        ) {
+            let word_list = WordList::embedded(list.clone()).unwrap();
+
+            let config =
+                Config::with_embedded(list.clone(), n, with_special_char)
+                    .checksum_word(true);
+            let passphrase = insecure::make_passphrase(config, seed).unwrap();
+
+            prop_assert_eq!(passphrase.split(' ').count(), n + 1);
+            prop_assert!(word_list.verify_checksum(&passphrase).unwrap());
+        }
+    }
+
+    #[test]
+    fn detects_a_typo_with_the_checksum_word() {
+        let word_list = WordList::embedded(EmbeddedList::EN).unwrap();
+
+        let config = Config::with_embedded(EmbeddedList::EN, 6, false)
+            .checksum_word(true);
+        let passphrase = insecure::make_passphrase(config, 42).unwrap();
+        let mut words: Vec<&str> = passphrase.split(' ').collect();
+        let checksum_word = words[6];
+
+        let swapped = format!("abbey {checksum_word}");
+
+        words[0] = "abbey";
+        let typo = words.join(" ");
+
+        assert!(word_list.verify_checksum(&passphrase).unwrap());
+        assert!(!word_list.verify_checksum(&typo).unwrap());
+        assert!(!word_list.verify_checksum(&swapped).unwrap());
+        assert!(!word_list.verify_checksum(checksum_word).unwrap());
+    }
+
+    #[test]
+    fn verifies_the_checksum_when_the_special_char_gives_another_word() {
+        let word_list = WordList::embedded(EmbeddedList::FR).unwrap();
+
+        // `266` can be `26` or `66` with a special character.
+        let index = word_list.index_of("26").unwrap();
+        let checksum_word = word_list.checksum_word(&[index]);
+        let passphrase = format!("266 {checksum_word}");
+
+        assert!(word_list.verify_checksum(&passphrase).unwrap());
+
+        // `12` is also in the list, but can be `1` with a special character.
+        let index = word_list.index_of("1").unwrap();
+        let checksum_word = word_list.checksum_word(&[index]);
+        let passphrase = format!("12 {checksum_word}");
+
+        assert!(word_list.verify_checksum(&passphrase).unwrap());
+    }
+
+    #[test]
+    fn computes_the_entropy() {
+        let config = Config::with_embedded(EmbeddedList::EN, 6, false);
+        assert!((config.entropy() - 77.548_875).abs() < 1e-6);
+
+        let config = Config::with_embedded(EmbeddedList::EN, 6, true)
+            .checksum_word(true);
+        assert!(
+            (config.entropy() - (77.548_875 + 36f64.log2() + 6f64.log2()))
+                .abs()
+                < 1e-6
+        );
+    }
+
+    #[test]
+    fn makes_a_passphrase_with_a_separator() {
+        let config =
+            Config::with_embedded(EmbeddedList::EN, 8, false).separator("-");
+        let with_separator = insecure::make_passphrase(config, 42).unwrap();
+
+        let config = Config::with_embedded(EmbeddedList::EN, 8, false);
+        let without_separator = insecure::make_passphrase(config, 42).unwrap();
+
+        assert_eq!(with_separator.replace('-', " "), without_separator);
+    }
+
+    #[test]
+    fn refuses_an_ambiguous_separator() {
+        let config =
+            Config::with_embedded(EmbeddedList::FR, 6, false).separator("");
+        let result = make_passphrase(config);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Word list: words joined with \"\" can be read in several ways"
+        );
+    }
+
+    #[test]
+    fn refuses_a_separator_containing_a_special_char() {
+        let config =
+            Config::with_embedded(EmbeddedList::EN, 6, true).separator("-");
+        let result = make_passphrase(config);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Word list: words joined with \"-\" can be read in several ways"
+        );
+
+        let config =
+            Config::with_embedded(EmbeddedList::EN, 6, false).separator("-");
+        assert!(make_passphrase(config).is_ok());
+    }
+
+    #[test]
+    fn config_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<Config<'_>>();
+    }
+
+    #[test]
+    fn makes_a_passphrase_from_a_reader() {
+        let content = embedded_list(&EmbeddedList::EN).join("\n");
+        let config = Config::with_reader(content.as_bytes(), 8, false);
+        let from_reader = insecure::make_passphrase(config, 42).unwrap();
+
+        let config = Config::with_embedded(EmbeddedList::EN, 8, false);
+        let embedded = insecure::make_passphrase(config, 42).unwrap();
+
+        assert_eq!(from_reader, embedded);
+    }
+
+    #[test]
+    fn makes_a_passphrase_from_a_word_list() {
+        let word_list = WordList::embedded(EmbeddedList::FR).unwrap();
+        let config = Config::with_word_list(word_list, 8, true);
+        let from_word_list = insecure::make_passphrase(config, 42).unwrap();
+
+        let config = Config::with_embedded(EmbeddedList::FR, 8, true);
+        let embedded = insecure::make_passphrase(config, 42).unwrap();
+
+        assert_eq!(from_word_list, embedded);
+    }
 }
@@ -0,0 +1,320 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use alloc::{borrow::Cow, format, string::String};
+use core::{fmt, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+/// Options for loading a word list.
+///
+/// # Example
+///
+/// ```rust
+/// use diceware::{LoadOptions, Normalization, WordList};
+///
+/// let options = LoadOptions::default().normalization(Normalization::NFKC);
+/// let word_list = WordList::from_file_with_options("words.txt", &options);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct LoadOptions {
+    normalization: Normalization,
+    ascii_folding: bool,
+    strict: bool,
+    encoding: Encoding,
+}
+
+/// Unicode normalization forms.
+///
+/// Words are normalized when a list is loaded, and before being looked up, so
+/// that a word typed in another form, like `é` as `e` followed by a combining
+/// accent, is still found in the list.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Normalization {
+    /// Canonical composition.
+    #[default]
+    NFC,
+
+    /// Compatibility composition, which also merges characters like `ﬁ` and
+    /// `fi`.
+    NFKC,
+}
+
+/// Text encodings of word list files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Encoding {
+    /// UTF-8.
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "utf-8"))]
+    Utf8,
+
+    /// ISO 8859-1, also known as Latin-1.
+    #[cfg_attr(feature = "serde", serde(rename = "latin1"))]
+    Latin1,
+
+    /// Windows-1252, a superset of the printable characters of Latin-1.
+    #[cfg_attr(feature = "serde", serde(rename = "windows-1252"))]
+    Windows1252,
+
+    /// UTF-8 if the file is valid UTF-8 or starts with a byte order mark,
+    /// Windows-1252 otherwise.
+    ///
+    /// As the whole content is needed to tell, a file or reader is read
+    /// entirely into memory before its lines are checked, instead of line by
+    /// line with the other encodings.
+    #[cfg_attr(feature = "serde", serde(rename = "auto"))]
+    Auto,
+}
+
+/// The characters of Windows-1252 from 0x80 to 0x9f. Undefined bytes are
+/// mapped to the C1 control characters, like Latin-1 does.
+const WINDOWS_1252: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ',
+    '\u{8d}', 'Ž', '\u{8f}', '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜',
+    '™', 'š', '›', 'œ', '\u{9d}', 'ž', 'Ÿ',
+];
+
+impl LoadOptions {
+    /// Sets the Unicode normalization form of the words.
+    pub fn normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    /// Transliterates the words to ASCII.
+    ///
+    /// Accents and other diacritics are removed, and some letters are
+    /// replaced, like `ß` by `ss` or `œ` by `oe`, so that a list can be typed
+    /// on any keyboard. Lists are refused if this gives duplicates or if a word
+    /// cannot be transliterated.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::LoadOptions;
+    ///
+    /// let options = LoadOptions::default().ascii_folding(true);
+    /// assert_eq!(options.normalize("Église"), "Eglise");
+    /// assert_eq!(options.normalize("Straße"), "Strasse");
+    /// ```
+    pub fn ascii_folding(mut self, ascii_folding: bool) -> Self {
+        self.ascii_folding = ascii_folding;
+        self
+    }
+
+    /// Reads list files strictly, with a word on every line.
+    ///
+    /// By default, comments and blank lines are ignored in list files. In
+    /// strict mode, they are read as words, so that blank lines are reported
+    /// as errors. See
+    /// [`WordList::from_file`](./struct.WordList.html#method.from_file) for
+    /// the file format.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets the text encoding of list files, which is UTF-8 by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::{Encoding, LoadOptions, WordList};
+    ///
+    /// let options = LoadOptions::default().encoding(Encoding::Latin1);
+    /// let word_list = WordList::from_file_with_options("words.txt", &options);
+    /// ```
+    pub fn encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Returns the Unicode normalization form of the words.
+    pub const fn normalization_form(&self) -> Normalization {
+        self.normalization
+    }
+
+    /// Returns whether the words are transliterated to ASCII.
+    pub const fn folds_to_ascii(&self) -> bool {
+        self.ascii_folding
+    }
+
+    /// Returns the text encoding of list files.
+    pub const fn text_encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Returns whether list files are read strictly.
+    pub const fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Normalizes a word like the words of a list loaded with these options.
+    pub fn normalize<'a>(&self, word: &'a str) -> Cow<'a, str> {
+        let normalized = self.normalization.normalize(word);
+
+        if self.ascii_folding && !normalized.is_ascii() {
+            Cow::Owned(fold_to_ascii(&normalized))
+        } else {
+            normalized
+        }
+    }
+}
+
+impl Normalization {
+    /// Normalizes a word.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::Normalization;
+    ///
+    /// assert_eq!(Normalization::NFC.normalize("e\u{301}"), "\u{e9}");
+    /// assert_eq!(Normalization::NFKC.normalize("\u{fb01}n"), "fin");
+    /// ```
+    pub fn normalize<'a>(&self, word: &'a str) -> Cow<'a, str> {
+        // ASCII words are normalized in every form.
+        if word.is_ascii() {
+            return Cow::Borrowed(word);
+        }
+
+        match self {
+            Self::NFC => Cow::Owned(word.nfc().collect()),
+            Self::NFKC => Cow::Owned(word.nfkc().collect()),
+        }
+    }
+}
+
+/// Decodes the content of a list file to UTF-8.
+pub fn decode(content: &[u8], encoding: Encoding) -> Cow<'_, [u8]> {
+    let from_bytes = |to_char: fn(u8) -> char| -> Cow<'_, [u8]> {
+        let text: String = content.iter().map(|&byte| to_char(byte)).collect();
+        Cow::Owned(text.into_bytes())
+    };
+
+    match encoding {
+        Encoding::Utf8 => Cow::Borrowed(content),
+        Encoding::Latin1 => from_bytes(char::from),
+        Encoding::Windows1252 => from_bytes(windows_1252),
+
+        Encoding::Auto => {
+            if content.starts_with(b"\xef\xbb\xbf")
+                || core::str::from_utf8(content).is_ok()
+            {
+                Cow::Borrowed(content)
+            } else {
+                from_bytes(windows_1252)
+            }
+        }
+    }
+}
+
+/// Decodes a Windows-1252 byte.
+fn windows_1252(byte: u8) -> char {
+    match byte {
+        0x80..=0x9f => WINDOWS_1252[usize::from(byte - 0x80)],
+        byte => char::from(byte),
+    }
+}
+
+/// Transliterates a word to ASCII, keeping the characters that cannot be
+/// transliterated.
+fn fold_to_ascii(word: &str) -> String {
+    let mut folded = String::with_capacity(word.len());
+
+    for c in word.nfd().filter(|&c| !is_combining_mark(c)) {
+        match c {
+            'ß' => folded.push_str("ss"),
+            'ẞ' => folded.push_str("SS"),
+            'æ' => folded.push_str("ae"),
+            'Æ' => folded.push_str("AE"),
+            'œ' => folded.push_str("oe"),
+            'Œ' => folded.push_str("OE"),
+            'ø' => folded.push('o'),
+            'Ø' => folded.push('O'),
+            'đ' | 'ð' => folded.push('d'),
+            'Đ' | 'Ð' => folded.push('D'),
+            'ł' => folded.push('l'),
+            'Ł' => folded.push('L'),
+            'ı' => folded.push('i'),
+            'þ' => folded.push_str("th"),
+            'Þ' => folded.push_str("TH"),
+            '’' | '‘' => folded.push('\''),
+            c => folded.push(c),
+        }
+    }
+
+    folded
+}
+
+impl fmt::Display for Normalization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NFC => write!(f, "NFC"),
+            Self::NFKC => write!(f, "NFKC"),
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Utf8 => write!(f, "utf-8"),
+            Self::Latin1 => write!(f, "latin1"),
+            Self::Windows1252 => write!(f, "windows-1252"),
+            Self::Auto => write!(f, "auto"),
+        }
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "utf-8" | "utf8" => Ok(Self::Utf8),
+            "latin1" | "latin-1" | "iso-8859-1" => Ok(Self::Latin1),
+            "windows-1252" | "cp1252" => Ok(Self::Windows1252),
+            "auto" => Ok(Self::Auto),
+            _ => Err(format!("{s}: unknown encoding")),
+        }
+    }
+}
+
+impl FromStr for Normalization {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "nfc" => Ok(Self::NFC),
+            "nfkc" => Ok(Self::NFKC),
+            _ => Err(format!("{s}: unknown normalization form")),
+        }
+    }
+}
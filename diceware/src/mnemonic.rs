@@ -0,0 +1,278 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Binary-to-words encoding with a checksum word.
+//!
+//! This module encodes arbitrary bytes, like a recovery key, as words from a
+//! Diceware word list, and decodes them back.
+//!
+//! # Format
+//!
+//! The data is read as a big-endian unsigned integer and written in base 7776,
+//! each digit being the index of a word in the list. Leading zero bytes are
+//! kept by always using the smallest number of words `n` so that `7776^n` is
+//! greater than or equal to `256^len`, padding with the first word of the list
+//! as needed.
+//!
+//! A final checksum word is appended. Its index is computed from the first
+//! four bytes of the SHA-256 hash of the data length (as a 64-bit big-endian
+//! integer) followed by the data. As the same number of words can encode two
+//! different data lengths, the checksum word also carries the parity of the
+//! data length:
+//!
+//! ```text
+//! checksum = (hash % 3888) * 2 + len % 2
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! use diceware::{mnemonic, EmbeddedList, WordList};
+//!
+//! let word_list = WordList::embedded(EmbeddedList::EN).unwrap();
+//! let key = [0x2a; 16];
+//!
+//! let words = mnemonic::encode(&word_list, &key);
+//! assert_eq!(words.split(' ').count(), 11);
+//!
+//! let decoded = mnemonic::decode(&word_list, &words).unwrap();
+//! assert_eq!(decoded, key);
+//! ```
+
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+
+use sha2::{Digest, Sha256};
+
+use crate::{LookupError, MnemonicError, Result, WordList, WORD_LIST_LENGTH};
+
+/// Encodes data as words from a word list, followed by a checksum word.
+pub fn encode(word_list: &WordList, data: &[u8]) -> String {
+    let mut digits = to_base_7776(data);
+    digits.push(checksum(data));
+
+    let words = word_list.words();
+    digits
+        .iter()
+        .map(|&digit| words[digit].as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Decodes words from a word list, followed by a checksum word, back to data.
+///
+/// Words are separated by whitespace.
+///
+/// # Errors
+///
+/// This function returns an error if a word is not in the list, if the number
+/// of words is invalid, if the words do not encode valid data or if the
+/// checksum does not match.
+pub fn decode(word_list: &WordList, mnemonic: &str) -> Result<Vec<u8>> {
+    let mut digits = mnemonic
+        .split_whitespace()
+        .map(|word| {
+            word_list
+                .index_of(word)
+                .ok_or_else(|| LookupError::UnknownWord(word.to_owned()).into())
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let checksum_digit = digits.pop().ok_or(MnemonicError::Empty)?;
+
+    let length = (0..=max_length(digits.len()))
+        .filter(|&length| word_count(length) == digits.len())
+        .find(|&length| length % 2 == checksum_digit % 2)
+        .ok_or(MnemonicError::InvalidLength(digits.len() + 1))?;
+
+    let data = from_base_7776(&digits, length)?;
+
+    if checksum(&data) != checksum_digit {
+        return Err(MnemonicError::InvalidChecksum.into());
+    }
+
+    Ok(data)
+}
+
+/// Returns the number of words needed to encode `length` bytes, excluding the
+/// checksum word.
+fn word_count(length: usize) -> usize {
+    // NOTE(cast): the number of bits is far below the precision of an f64 for
+    // any realistic data length, and log2(7776) is irrational so that the
+    // ratio is never an exact integer (except for 0).
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let count =
+        libm::ceil((length * 8) as f64 / libm::log2(WORD_LIST_LENGTH as f64))
+            as usize;
+
+    count
+}
+
+/// Returns the maximum number of bytes that `words` words can encode.
+fn max_length(words: usize) -> usize {
+    words * 13 / 8
+}
+
+/// Computes the checksum word index.
+fn checksum(data: &[u8]) -> usize {
+    let mut hasher = Sha256::new();
+    hasher.update((data.len() as u64).to_be_bytes());
+    hasher.update(data);
+    let hash = hasher.finalize();
+
+    let value = u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]]);
+    let half = WORD_LIST_LENGTH / 2;
+
+    (value as usize % half) * 2 + data.len() % 2
+}
+
+/// Converts big-endian bytes to base 7776 digits, padded to the word count.
+fn to_base_7776(data: &[u8]) -> Vec<usize> {
+    let mut number = data.to_vec();
+    let mut digits = Vec::with_capacity(word_count(data.len()));
+
+    while number.iter().any(|&byte| byte != 0) {
+        let mut remainder = 0;
+        for byte in &mut number {
+            let value = remainder * 256 + *byte as usize;
+            // NOTE(cast): value / 7776 is less than 256 as remainder < 7776.
+            *byte = (value / WORD_LIST_LENGTH) as u8;
+            remainder = value % WORD_LIST_LENGTH;
+        }
+        digits.push(remainder);
+    }
+
+    digits.resize(word_count(data.len()), 0);
+    digits.reverse();
+    digits
+}
+
+/// Converts base 7776 digits to `length` big-endian bytes.
+fn from_base_7776(digits: &[usize], length: usize) -> Result<Vec<u8>> {
+    let mut data = vec![0u8; length];
+
+    for &digit in digits {
+        let mut carry = digit;
+        for byte in data.iter_mut().rev() {
+            let value = *byte as usize * WORD_LIST_LENGTH + carry;
+            // NOTE(cast): value % 256 is always less than 256.
+            *byte = (value % 256) as u8;
+            carry = value / 256;
+        }
+
+        if carry != 0 {
+            return Err(MnemonicError::InvalidPadding.into());
+        }
+    }
+
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::EmbeddedList;
+    use proptest::prelude::*;
+
+    fn word_list() -> WordList {
+        WordList::embedded(EmbeddedList::EN).unwrap()
+    }
+
+    proptest! {
+        #[test]
+        fn decodes_encoded_data(
+            data in proptest::collection::vec(any::<u8>(), 0..64)
+        ) {
+            let word_list = word_list();
+            let words = encode(&word_list, &data);
+
+            prop_assert_eq!(
+                words.split(' ').count(),
+                word_count(data.len()) + 1
+            );
+            prop_assert_eq!(decode(&word_list, &words).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn keeps_leading_zeros() {
+        let word_list = word_list();
+
+        for length in 0..32 {
+            let data = vec![0; length];
+            let words = encode(&word_list, &data);
+
+            assert_eq!(decode(&word_list, &words).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn encodes_128_bits_in_11_words() {
+        let words = encode(&word_list(), &[0xff; 16]);
+        assert_eq!(words.split(' ').count(), 11);
+    }
+
+    #[test]
+    fn returns_an_error_if_the_checksum_is_invalid() {
+        let word_list = word_list();
+        let words = encode(&word_list, b"diceware");
+
+        let mut words: Vec<&str> = words.split(' ').collect();
+        words[0] = if words[0] == "a" { "a&p" } else { "a" };
+        let result = decode(&word_list, &words.join(" "));
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Mnemonic: invalid checksum"
+        );
+    }
+
+    #[test]
+    fn returns_an_error_if_there_are_no_words() {
+        let result = decode(&word_list(), "  ");
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().to_string(), "Mnemonic: no words");
+    }
+
+    #[test]
+    fn returns_an_error_if_a_word_is_unknown() {
+        let result = decode(&word_list(), "abbey notaword");
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Lookup: notaword: unknown word"
+        );
+    }
+
+    #[test]
+    fn returns_an_error_if_the_padding_is_invalid() {
+        // One word can only encode one byte, so the last word of the list is
+        // out of range.
+        let word_list = word_list();
+        let checksum_word = &word_list.words()[1];
+        let result = decode(&word_list, &format!("zzzz {checksum_word}"));
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Mnemonic: invalid padding"
+        );
+    }
+}
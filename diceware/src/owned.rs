@@ -0,0 +1,238 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use alloc::string::String;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Config, EmbeddedList, LoadOptions};
+
+/// An owned configuration for the passphrase generator.
+///
+/// Unlike [`Config`](./struct.Config.html), which borrows the file name of the
+/// word list and can read it from any reader, this configuration owns all its
+/// fields, so that it can be stored. With the `serde` feature, it can be
+/// serialized, for instance to store generation policies in JSON or TOML:
+///
+/// ```toml
+/// words = 8
+/// with_special_char = true
+/// separator = "-"
+/// word_list = { embedded = "en" }
+/// ```
+///
+/// Every field but `word_list` and `words` is optional.
+///
+/// # Example
+///
+/// ```rust
+/// use diceware::{EmbeddedList, ListSource, OwnedConfig};
+///
+/// let mut owned = OwnedConfig::new(ListSource::Embedded(EmbeddedList::EN), 8);
+/// owned.separator = String::from("-");
+///
+/// let passphrase = diceware::make_passphrase(owned.config()).unwrap();
+/// assert_eq!(passphrase.split('-').count(), 8);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct OwnedConfig {
+    /// The source of the word list.
+    pub word_list: ListSource,
+
+    /// The number of words to generate.
+    pub words: usize,
+
+    /// Whether to insert a special character in a word.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub with_special_char: bool,
+
+    /// Whether to append a checksum word.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub checksum_word: bool,
+
+    /// The separator between words.
+    #[cfg_attr(feature = "serde", serde(default = "default_separator"))]
+    pub separator: String,
+
+    /// The options used to load the word list.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub load_options: LoadOptions,
+}
+
+/// The source of a word list in an [`OwnedConfig`](./struct.OwnedConfig.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "snake_case")
+)]
+#[non_exhaustive]
+pub enum ListSource {
+    /// An embedded word list.
+    Embedded(EmbeddedList),
+
+    /// A word list file.
+    #[cfg(feature = "std")]
+    File(String),
+}
+
+impl OwnedConfig {
+    /// Creates an owned configuration with the default options: no special
+    /// character, no checksum word and words separated by a space.
+    pub fn new(word_list: ListSource, words: usize) -> Self {
+        Self {
+            word_list,
+            words,
+            with_special_char: false,
+            checksum_word: false,
+            separator: default_separator(),
+            load_options: LoadOptions::default(),
+        }
+    }
+
+    /// Returns the corresponding configuration.
+    pub fn config(&self) -> Config<'_> {
+        let config = match &self.word_list {
+            ListSource::Embedded(list) => Config::with_embedded(
+                list.clone(),
+                self.words,
+                self.with_special_char,
+            ),
+
+            #[cfg(feature = "std")]
+            ListSource::File(filename) => Config::with_filename(
+                filename,
+                self.words,
+                self.with_special_char,
+            ),
+        };
+
+        config
+            .checksum_word(self.checksum_word)
+            .separator(&self.separator)
+            .load_options(self.load_options.clone())
+    }
+}
+
+/// Returns the default separator between words.
+fn default_separator() -> String {
+    String::from(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::insecure;
+
+    #[test]
+    fn makes_the_same_passphrase_as_the_config() {
+        let mut owned =
+            OwnedConfig::new(ListSource::Embedded(EmbeddedList::EN), 6);
+        owned.with_special_char = true;
+        owned.checksum_word = true;
+        owned.separator = String::from("_");
+
+        let config = Config::with_embedded(EmbeddedList::EN, 6, true)
+            .checksum_word(true)
+            .separator("_");
+
+        assert_eq!(
+            insecure::make_passphrase(owned.config(), 42).unwrap(),
+            insecure::make_passphrase(config, 42).unwrap()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserializes_a_minimal_config_from_toml() {
+        let owned: OwnedConfig = toml::from_str(
+            r#"
+                words = 6
+                word_list = { file = "words.txt" }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            owned,
+            OwnedConfig::new(ListSource::File(String::from("words.txt")), 6)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserializes_a_full_config_from_json() {
+        let owned: OwnedConfig = serde_json::from_str(
+            r#"{
+                "word_list": { "embedded": "fr" },
+                "words": 8,
+                "with_special_char": true,
+                "checksum_word": true,
+                "separator": "-",
+                "load_options": {
+                    "normalization": "nfkc",
+                    "ascii_folding": true,
+                    "strict": true,
+                    "encoding": "windows-1252"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let mut expected =
+            OwnedConfig::new(ListSource::Embedded(EmbeddedList::FR), 8);
+        expected.with_special_char = true;
+        expected.checksum_word = true;
+        expected.separator = String::from("-");
+        expected.load_options = LoadOptions::default()
+            .normalization(crate::Normalization::NFKC)
+            .ascii_folding(true)
+            .strict(true)
+            .encoding(crate::Encoding::Windows1252);
+
+        assert_eq!(owned, expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn round_trips_through_json() {
+        let owned = OwnedConfig::new(ListSource::Embedded(EmbeddedList::EN), 6);
+        let json = serde_json::to_string(&owned).unwrap();
+        assert_eq!(serde_json::from_str::<OwnedConfig>(&json).unwrap(), owned);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn refuses_unknown_fields() {
+        let result = toml::from_str::<OwnedConfig>(
+            r#"
+                words = 6
+                word_list = { embedded = "en" }
+                special_char = true
+            "#,
+        );
+
+        assert!(result.is_err());
+    }
+}
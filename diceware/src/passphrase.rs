@@ -0,0 +1,174 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A generated passphrase, with its structure.
+///
+/// The passphrase itself is given by its `Display` implementation, which joins
+/// the words and the checksum word with the separator.
+///
+/// # Example
+///
+/// ```rust
+/// use diceware::{Config, EmbeddedList};
+///
+/// let config = Config::with_embedded(EmbeddedList::EN, 6, true);
+/// let passphrase = diceware::passphrases(config).unwrap().next_passphrase();
+///
+/// let special_char = passphrase.special_char.unwrap();
+/// let word = &passphrase.words[special_char.word];
+/// let character = word[special_char.position..].chars().next();
+/// assert_eq!(character, Some(special_char.character));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Passphrase {
+    /// The words of the passphrase, without the checksum word.
+    ///
+    /// If a special character has been inserted, it is part of its word.
+    pub words: Vec<String>,
+
+    /// The special character inserted in a word, if any.
+    pub special_char: Option<InsertedSpecialChar>,
+
+    /// The checksum word, if any.
+    pub checksum_word: Option<String>,
+
+    /// The separator between words.
+    pub separator: String,
+
+    /// The entropy of the passphrase, in bits.
+    pub entropy: f64,
+}
+
+/// A special character inserted in a passphrase word.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InsertedSpecialChar {
+    /// The index of the word in the passphrase.
+    pub word: usize,
+
+    /// The special character.
+    pub character: char,
+
+    /// The byte position of the special character in the word.
+    pub position: usize,
+}
+
+impl fmt::Display for Passphrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let words = self.words.iter().chain(&self.checksum_word);
+
+        for (i, word) in words.enumerate() {
+            if i > 0 {
+                f.write_str(&self.separator)?;
+            }
+
+            f.write_str(word)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::{insecure, Config, EmbeddedList, WordList};
+    use alloc::string::ToString;
+
+    #[test]
+    fn displays_the_passphrase() {
+        let config = Config::with_embedded(EmbeddedList::EN, 6, true)
+            .checksum_word(true)
+            .separator("_");
+        let passphrase =
+            insecure::passphrases(config, 42).unwrap().next_passphrase();
+
+        let config = Config::with_embedded(EmbeddedList::EN, 6, true)
+            .checksum_word(true)
+            .separator("_");
+        let expected = insecure::make_passphrase(config, 42).unwrap();
+
+        assert_eq!(passphrase.to_string(), expected);
+    }
+
+    #[test]
+    fn describes_the_passphrase() {
+        let config = Config::with_embedded(EmbeddedList::EN, 6, true)
+            .checksum_word(true);
+        let passphrase =
+            insecure::passphrases(config, 42).unwrap().next_passphrase();
+
+        let word_list = WordList::embedded(EmbeddedList::EN).unwrap();
+        let special_char = passphrase.special_char.unwrap();
+        let indices: Vec<usize> = passphrase
+            .words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| {
+                let mut word = word.clone();
+                if i == special_char.word {
+                    let removed = word.remove(special_char.position);
+                    assert_eq!(removed, special_char.character);
+                }
+                word_list.index_of(&word).unwrap()
+            })
+            .collect();
+
+        assert_eq!(passphrase.words.len(), 6);
+        assert_eq!(
+            passphrase.checksum_word.as_deref(),
+            Some(word_list.checksum_word(&indices))
+        );
+        assert_eq!(passphrase.separator, " ");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_the_passphrase() {
+        let passphrase = Passphrase {
+            words: vec![String::from("a1"), String::from("b")],
+            special_char: Some(InsertedSpecialChar {
+                word: 0,
+                character: '1',
+                position: 1,
+            }),
+            checksum_word: None,
+            separator: String::from(" "),
+            entropy: 30.5,
+        };
+
+        let json = serde_json::to_value(&passphrase).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "words": ["a1", "b"],
+                "special_char": { "word": 0, "character": "1", "position": 1 },
+                "checksum_word": null,
+                "separator": " ",
+                "entropy": 30.5,
+            })
+        );
+    }
+}
@@ -0,0 +1,318 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec,
+    vec::Vec,
+};
+use core::cmp::Ordering;
+
+use crate::WordList;
+
+/// The number of characters used to compare word prefixes.
+pub const PREFIX_LENGTH: usize = 3;
+
+/// Statistics about a word list.
+///
+/// Lengths and edit distances are counted in characters. More statistics may
+/// be added, so `Stats` can only be built by [`WordList::stats`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Stats {
+    /// The length of the shortest word.
+    pub min_length: usize,
+
+    /// The average word length.
+    pub average_length: f64,
+
+    /// The length of the longest word.
+    pub max_length: usize,
+
+    /// The characters used in the list.
+    pub charset: BTreeSet<char>,
+
+    /// The non-ASCII characters used in the list.
+    pub non_ascii: BTreeSet<char>,
+
+    /// The words containing non-alphabetic characters, like `a&p` or `1492`.
+    pub non_alphabetic: Vec<String>,
+
+    /// The number of words that are a prefix of another word.
+    pub prefix_words: usize,
+
+    /// The number of words sharing their first three characters with another
+    /// word.
+    pub shared_prefixes: usize,
+
+    /// The minimum edit distance between two words.
+    pub min_edit_distance: usize,
+
+    /// The first pair of words found at the minimum edit distance.
+    pub closest_words: (String, String),
+}
+
+impl WordList {
+    /// Computes statistics about the word list.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::{EmbeddedList, WordList};
+    ///
+    /// let stats = WordList::embedded(EmbeddedList::EN).unwrap().stats();
+    ///
+    /// assert_eq!(stats.min_edit_distance, 1);
+    /// assert!(stats.non_alphabetic.contains(&String::from("a&p")));
+    /// ```
+    pub fn stats(&self) -> Stats {
+        let words = self.words();
+        let lengths: Vec<usize> =
+            words.iter().map(|word| word.chars().count()).collect();
+
+        let charset: BTreeSet<char> =
+            words.iter().flat_map(|word| word.chars()).collect();
+
+        let non_ascii =
+            charset.iter().copied().filter(|c| !c.is_ascii()).collect();
+
+        let non_alphabetic = words
+            .iter()
+            .filter(|word| !word.chars().all(char::is_alphabetic))
+            .cloned()
+            .collect();
+
+        let (min_edit_distance, closest_words) = min_edit_distance(words);
+
+        Stats {
+            // NOTE(unwrap): a word list is never empty.
+            #[allow(clippy::unwrap_used)]
+            min_length: *lengths.iter().min().unwrap(),
+            average_length: lengths.iter().sum::<usize>() as f64
+                / lengths.len() as f64,
+            // NOTE(unwrap): a word list is never empty.
+            #[allow(clippy::unwrap_used)]
+            max_length: *lengths.iter().max().unwrap(),
+            charset,
+            non_ascii,
+            non_alphabetic,
+            prefix_words: prefix_words(words),
+            shared_prefixes: shared_prefixes(words),
+            min_edit_distance,
+            closest_words,
+        }
+    }
+}
+
+/// Counts the words that are a prefix of another word.
+fn prefix_words(words: &[String]) -> usize {
+    let mut sorted: Vec<&str> = words.iter().map(AsRef::as_ref).collect();
+    sorted.sort_unstable();
+
+    // In a sorted list, the words starting with a given word directly follow
+    // it.
+    sorted
+        .windows(2)
+        .filter(|pair| pair[1].starts_with(pair[0]))
+        .count()
+}
+
+/// Counts the words sharing their first characters with another word.
+fn shared_prefixes(words: &[String]) -> usize {
+    let mut prefixes = BTreeMap::<String, usize>::new();
+
+    for word in words {
+        if word.chars().count() >= PREFIX_LENGTH {
+            let prefix = word.chars().take(PREFIX_LENGTH).collect();
+            *prefixes.entry(prefix).or_default() += 1;
+        }
+    }
+
+    prefixes.values().filter(|&&count| count > 1).sum()
+}
+
+/// Computes the minimum edit distance between two words of a list.
+///
+/// Pairs of words are only compared when two lower bounds of their edit
+/// distance, the difference of their lengths and the number of characters
+/// not shared by both, are less than the minimum found so far.
+fn min_edit_distance(words: &[String]) -> (usize, (String, String)) {
+    let mut words: Vec<Vec<char>> =
+        words.iter().map(|word| word.chars().collect()).collect();
+    words.sort_by_key(Vec::len);
+
+    let sorted_chars: Vec<Vec<char>> = words
+        .iter()
+        .map(|word| {
+            let mut chars = word.clone();
+            chars.sort_unstable();
+            chars
+        })
+        .collect();
+
+    let mut best = usize::MAX;
+    let mut closest = (0, 0);
+
+    'outer: for i in 0..words.len() {
+        for j in (i + 1)..words.len() {
+            // Words are sorted by length, and the edit distance is at least
+            // the length difference.
+            if words[j].len() - words[i].len() >= best {
+                break;
+            }
+
+            if bag_distance(&sorted_chars[i], &sorted_chars[j]) >= best {
+                continue;
+            }
+
+            let distance = edit_distance(&words[i], &words[j], best);
+            if distance < best {
+                best = distance;
+                closest = (i, j);
+
+                // Words are unique, so the distance cannot be lower than 1.
+                if best == 1 {
+                    break 'outer;
+                }
+            }
+        }
+    }
+
+    let word = |i: usize| words[i].iter().collect();
+    (best, (word(closest.0), word(closest.1)))
+}
+
+/// Computes the bag distance between two words given their sorted characters.
+///
+/// The bag distance is the largest number of characters of one word that are
+/// not in the other one, counted with their multiplicity. It is a lower bound
+/// of the Levenshtein distance, as each edit changes at most one character of
+/// each word.
+fn bag_distance(a: &[char], b: &[char]) -> usize {
+    let (mut i, mut j) = (0, 0);
+    let (mut only_a, mut only_b) = (0, 0);
+
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            Ordering::Less => {
+                only_a += 1;
+                i += 1;
+            }
+            Ordering::Greater => {
+                only_b += 1;
+                j += 1;
+            }
+            Ordering::Equal => {
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+
+    (only_a + a.len() - i).max(only_b + b.len() - j)
+}
+
+/// Computes the Levenshtein distance between two words.
+///
+/// The computation stops as soon as the distance reaches `bound`, returning a
+/// value greater than or equal to `bound`.
+fn edit_distance(a: &[char], b: &[char], bound: usize) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] =
+                substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        if current.iter().min().is_some_and(|&min| min >= bound) {
+            return bound;
+        }
+
+        core::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::{test_util::word_list, EmbeddedList};
+
+    #[test]
+    fn computes_the_edit_distance() {
+        let distance = |a: &str, b: &str| {
+            let a: Vec<char> = a.chars().collect();
+            let b: Vec<char> = b.chars().collect();
+            edit_distance(&a, &b, usize::MAX)
+        };
+
+        assert_eq!(distance("kitten", "sitting"), 3);
+        assert_eq!(distance("", "abc"), 3);
+        assert_eq!(distance("église", "eglise"), 1);
+        assert_eq!(distance("abc", "abc"), 0);
+    }
+
+    #[test]
+    fn computes_the_bag_distance() {
+        let distance = |a: &str, b: &str| {
+            let mut a: Vec<char> = a.chars().collect();
+            let mut b: Vec<char> = b.chars().collect();
+            a.sort_unstable();
+            b.sort_unstable();
+            bag_distance(&a, &b)
+        };
+
+        assert_eq!(distance("kitten", "sitting"), 3);
+        assert_eq!(distance("abc", "cba"), 0);
+        assert_eq!(distance("", "abc"), 3);
+        assert_eq!(distance("aab", "abb"), 1);
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = word_list(&["été", "étés", "abcdefghijklmnopq"]).stats();
+
+        assert_eq!(stats.min_length, 3);
+        assert_eq!(stats.max_length, 17);
+        assert_eq!(stats.non_ascii, BTreeSet::from(['é']));
+        assert!(stats.charset.contains(&'q'));
+        assert_eq!(stats.prefix_words, 1);
+        assert_eq!(stats.min_edit_distance, 1);
+        assert_eq!(
+            stats.closest_words,
+            (String::from("été"), String::from("étés"))
+        );
+    }
+
+    #[test]
+    fn exposes_non_alphabetic_words_in_the_english_list() {
+        let stats = WordList::embedded(EmbeddedList::EN).unwrap().stats();
+
+        for word in ["a&p", "1492", "$$"] {
+            assert!(stats.non_alphabetic.contains(&word.to_owned()));
+        }
+        assert!(stats.non_ascii.is_empty());
+    }
+}
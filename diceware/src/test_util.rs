@@ -0,0 +1,66 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Helpers shared by the unit tests.
+
+#![allow(clippy::unwrap_used)]
+
+use alloc::{borrow::ToOwned, format, string::String};
+
+use proptest::prelude::*;
+
+use crate::{EmbeddedList, WordList, WORD_LIST_LENGTH};
+
+/// Arbitrary embedded word list generator.
+pub fn arb_list() -> BoxedStrategy<EmbeddedList> {
+    prop_oneof![Just(EmbeddedList::EN), Just(EmbeddedList::FR)].boxed()
+}
+
+/// Builds a valid word list file content.
+pub fn valid_content() -> String {
+    (0..WORD_LIST_LENGTH)
+        .map(|i| format!("word{i}\n"))
+        .collect()
+}
+
+/// Builds a word list of three lowercase letters, without any special
+/// character.
+pub fn letter_list() -> WordList {
+    let words = (0..WORD_LIST_LENGTH)
+        .map(|i| {
+            [i / 676, i / 26 % 26, i % 26]
+                .iter()
+                .map(|&letter| char::from(b'a' + letter as u8))
+                .collect()
+        })
+        .collect();
+
+    WordList::new(words).unwrap()
+}
+
+/// Builds a word list from tripled numbers and some extra words.
+///
+/// The numbers all have the same length and only contain digits, so that they
+/// neither share prefixes nor can be read in several ways when joined.
+pub fn word_list(extra: &[&str]) -> WordList {
+    let words = (0..WORD_LIST_LENGTH - extra.len())
+        .map(|i| format!("{i:05}"))
+        .map(|w| w.chars().flat_map(|c| [c, c, c]).collect())
+        .chain(extra.iter().map(|&w| w.to_owned()))
+        .collect();
+
+    WordList::new(words).unwrap()
+}
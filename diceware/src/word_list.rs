@@ -0,0 +1,1863 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use alloc::{
+    borrow::{Cow, ToOwned},
+    collections::{btree_map::Entry, BTreeMap},
+    string::String,
+    vec,
+    vec::Vec,
+};
+
+#[cfg(feature = "std")]
+use std::{
+    fs::File,
+    io::{self, BufRead, Read},
+    path::Path,
+};
+
+use sha2::{Digest, Sha256};
+use unicode_security::{skeleton, MixedScript};
+
+use crate::{
+    embedded, load, Diagnostic, DiagnosticKind, EmbeddedList, Encoding,
+    LoadOptions, LookupError, Result, WordListError, SPECIAL_CHARS,
+};
+
+#[cfg(feature = "std")]
+use crate::{compression, Error};
+
+/// The number of words in a Diceware word list.
+pub const WORD_LIST_LENGTH: usize = 7776;
+
+/// The UTF-8 byte order mark.
+const BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// The index of the words, to look them up: a hash map with the `std`
+/// feature, an ordered map without it.
+#[cfg(feature = "std")]
+type Index = std::collections::HashMap<String, usize>;
+#[cfg(not(feature = "std"))]
+type Index = BTreeMap<String, usize>;
+
+/// A checked Diceware word list.
+///
+/// A word list is checked when it is created: it must contain exactly 7776
+/// unique words. It is then indexed in a hash map, so that words can be looked
+/// up back to their index and dice rolls in constant time. Without the `std`
+/// feature, the index is an ordered map.
+///
+/// Words are normalized when the list is created, and before being looked up.
+/// See [`LoadOptions`](./struct.LoadOptions.html).
+#[derive(Clone, Debug)]
+pub struct WordList {
+    words: Vec<String>,
+    index: Index,
+    options: LoadOptions,
+}
+
+/// The result of looking up a passphrase word in a word list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WordLookup {
+    /// The word from the list.
+    pub word: String,
+
+    /// The index of the word in the list.
+    pub index: usize,
+
+    /// The five dice rolls corresponding to the word.
+    pub rolls: [u8; 5],
+
+    /// The special character inserted in the word, if any.
+    pub special_char: Option<SpecialChar>,
+}
+
+/// A special character inserted in a word.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpecialChar {
+    /// The special character.
+    pub character: char,
+
+    /// The byte position of the special character in the passphrase word.
+    pub position: usize,
+}
+
+impl WordList {
+    /// Creates a word list from a vector of words.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the list is not 7776-word long or if
+    /// it contains duplicates. The error contains a
+    /// [`Diagnostic`](./struct.Diagnostic.html) for each problem, the line
+    /// being the position of the word in the list.
+    pub fn new(words: Vec<String>) -> Result<Self> {
+        Self::new_with_options(words, &LoadOptions::default())
+    }
+
+    /// Creates a word list from a vector of words, with custom load options.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the list is not 7776-word long or if
+    /// it contains duplicates after normalization. See
+    /// [`WordList::new`](#method.new).
+    pub fn new_with_options(
+        words: Vec<String>,
+        options: &LoadOptions,
+    ) -> Result<Self> {
+        let lines = (1..=words.len()).collect::<Vec<_>>();
+        Self::check(words, &lines, vec![], options)
+    }
+
+    /// Reads a word list from a file, featuring a word by line.
+    ///
+    /// # Format
+    ///
+    /// A word list file is a UTF-8 text file, or a text file in
+    /// [another encoding](./struct.LoadOptions.html#method.encoding), with a
+    /// word on each line:
+    ///
+    /// * files compressed with gzip, Zstandard or xz are decompressed
+    ///   transparently, if the `gzip`, `zstd` or `xz` cargo feature is
+    ///   enabled. They are detected by their magic bytes,
+    /// * a byte order mark at the start of the file is ignored,
+    /// * lines can end with `\n` or `\r\n`,
+    /// * blank lines, and comments starting with `#` followed by a space or a
+    ///   tab, are ignored, unless the list is read in
+    ///   [strict mode](./struct.LoadOptions.html#method.strict). A single `#`
+    ///   is a word, as it is in the original Diceware list,
+    /// * lines can optionally be numbered with their dice rolls, like in the
+    ///   original Diceware list: `11111 a`. In this case, every word must be
+    ///   numbered, and the dice rolls must match the word position.
+    ///
+    /// Line numbers in diagnostics count every line of the file, including
+    /// comments and blank lines.
+    ///
+    /// ```text
+    /// # My word list, version 1.0
+    /// 11111 a
+    /// 11112 a&p
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the file cannot be read or if the
+    /// word list is invalid. In the latter case, the error contains a
+    /// [`Diagnostic`](./struct.Diagnostic.html) for each problem found in the
+    /// file. In both cases, the error contains the path of the file.
+    #[cfg(feature = "std")]
+    pub fn from_file(filename: impl AsRef<Path>) -> Result<Self> {
+        Self::from_file_with_options(filename, &LoadOptions::default())
+    }
+
+    /// Reads a word list from a file, with custom load options.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the file cannot be read or if the
+    /// word list is invalid. See [`WordList::from_file`](#method.from_file).
+    #[cfg(feature = "std")]
+    pub fn from_file_with_options(
+        filename: impl AsRef<Path>,
+        options: &LoadOptions,
+    ) -> Result<Self> {
+        let path = filename.as_ref();
+
+        File::open(path)
+            .map_err(Error::from)
+            .and_then(|file| Self::from_reader(file, options))
+            .map_err(|err| with_path(err, path))
+    }
+
+    /// Reads a word list from a reader, like the standard input, with custom
+    /// load options.
+    ///
+    /// The content is read in the same format as a word list file, and can be
+    /// compressed. See [`WordList::from_file`](#method.from_file).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::{LoadOptions, WordList};
+    ///
+    /// let content: String = (0..7776).map(|i| format!("{i}\n")).collect();
+    /// let word_list =
+    ///     WordList::from_reader(content.as_bytes(), &LoadOptions::default())
+    ///         .unwrap();
+    ///
+    /// assert_eq!(word_list.index_of("42"), Some(42));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the content cannot be read or if the
+    /// word list is invalid. Errors contain no path.
+    #[cfg(feature = "std")]
+    pub fn from_reader(
+        reader: impl Read,
+        options: &LoadOptions,
+    ) -> Result<Self> {
+        let (words, lines, diagnostics) = read_stream(reader, options)?;
+        Self::check(words, &lines, diagnostics, options)
+    }
+
+    /// Parses a word list from the content of a word list file, with custom
+    /// load options.
+    ///
+    /// The content is read in the same format as a word list file, but cannot
+    /// be compressed. See [`WordList::from_file`](#method.from_file). This is
+    /// available without the `std` feature, for instance to use a list stored
+    /// in flash memory.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::{LoadOptions, WordList};
+    ///
+    /// let content: String = (0..7776).map(|i| format!("{i}\n")).collect();
+    /// let word_list =
+    ///     WordList::from_bytes(content.as_bytes(), &LoadOptions::default())
+    ///         .unwrap();
+    ///
+    /// assert_eq!(word_list.index_of("42"), Some(42));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the word list is invalid. The error
+    /// contains a [`Diagnostic`](./struct.Diagnostic.html) for each problem
+    /// found.
+    pub fn from_bytes(content: &[u8], options: &LoadOptions) -> Result<Self> {
+        let encoding = options.text_encoding();
+        let content = load::decode(skip_bom(content, encoding), encoding);
+        let (words, lines, diagnostics) = read_lines(&content, options);
+        Self::check(words, &lines, diagnostics, options)
+    }
+
+    /// Normalizes the words, checks each word and the length of the list and
+    /// looks for duplicates, then builds the word list if no problem has been
+    /// found.
+    fn check(
+        words: Vec<String>,
+        lines: &[usize],
+        mut diagnostics: Vec<Diagnostic>,
+        options: &LoadOptions,
+    ) -> Result<Self> {
+        let words = normalize(words, options);
+        check_blanks(&words, lines, &mut diagnostics);
+        check_folding(&words, lines, options, &mut diagnostics);
+        let index = check_words(&words, lines, &mut diagnostics);
+
+        if diagnostics.is_empty() {
+            Ok(Self {
+                words,
+                index,
+                options: options.clone(),
+            })
+        } else {
+            sort_diagnostics(&mut diagnostics);
+            Err(WordListError::Invalid(diagnostics).into())
+        }
+    }
+
+    /// Gets an embedded word list.
+    ///
+    /// Embedded word lists are checked like any other list, so you do not have
+    /// to trust them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::{EmbeddedList, WordList};
+    ///
+    /// let word_list = WordList::embedded(EmbeddedList::EN).unwrap();
+    /// assert_eq!(word_list.index_of("abbey"), Some(16));
+    /// ```
+    pub fn embedded(list: EmbeddedList) -> Result<Self> {
+        Self::embedded_with_options(list, &LoadOptions::default())
+    }
+
+    /// Gets an embedded word list, with custom load options.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the list is invalid with these
+    /// options.
+    pub fn embedded_with_options(
+        list: EmbeddedList,
+        options: &LoadOptions,
+    ) -> Result<Self> {
+        let words = embedded_list(&list).iter().map(|&w| w.into()).collect();
+        Self::new_with_options(words, options)
+    }
+
+    /// Returns the words in the list.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Returns the fingerprint of the list.
+    ///
+    /// The fingerprint is the SHA-256 hash of the words, in order, each one
+    /// followed by a line feed. As it is computed on the loaded words, it does
+    /// not depend on the file format: a numbered list, a compressed file or a
+    /// file with comments have the same fingerprint as the plain list.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::WordList;
+    ///
+    /// let words: Vec<String> = (0..7776).map(|i| i.to_string()).collect();
+    /// let a = WordList::new(words.clone()).unwrap();
+    /// let b = WordList::new(words).unwrap();
+    ///
+    /// assert_eq!(a.fingerprint(), b.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        for word in &self.words {
+            hasher.update(word.as_bytes());
+            hasher.update(b"\n");
+        }
+
+        hasher.finalize().into()
+    }
+
+    /// Returns the index of a word in the list, if present.
+    ///
+    /// The word is normalized like the words of the list before being looked
+    /// up.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::WordList;
+    ///
+    /// let mut words: Vec<String> = (0..7775).map(|i| i.to_string()).collect();
+    /// words.push(String::from("\u{e9}t\u{e9}"));
+    /// let word_list = WordList::new(words).unwrap();
+    ///
+    /// assert_eq!(word_list.index_of("e\u{301}te\u{301}"), Some(7775));
+    /// ```
+    pub fn index_of(&self, word: &str) -> Option<usize> {
+        self.index.get(&*self.options.normalize(word)).copied()
+    }
+
+    /// Looks up a passphrase word in the list.
+    ///
+    /// If the word is not in the list, it is considered as containing an
+    /// inserted special character. In this case, the lookup succeeds if
+    /// removing one special character from the word gives a unique word from
+    /// the list.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the word cannot be found in the list,
+    /// even after removing a special character, or if removing a special
+    /// character can give several words from the list.
+    pub fn lookup_word(&self, word: &str) -> Result<WordLookup> {
+        if let Some(index) = self.index_of(word) {
+            return Ok(self.word_lookup(index, None));
+        }
+
+        match self.special_char_candidates(word)[..] {
+            [(index, special_char)] => {
+                Ok(self.word_lookup(index, Some(special_char)))
+            }
+            [] => Err(LookupError::UnknownWord(word.to_owned()).into()),
+            _ => Err(LookupError::AmbiguousWord(word.to_owned()).into()),
+        }
+    }
+
+    /// Looks up each word of a passphrase in the list.
+    ///
+    /// Words in the passphrase are separated by whitespace. See
+    /// [`WordList::lookup_word`](#method.lookup_word) for details about the
+    /// lookup of each word.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::{EmbeddedList, WordList};
+    ///
+    /// let word_list = WordList::embedded(EmbeddedList::EN).unwrap();
+    /// let lookup = word_list.lookup("abbey ab#ide").unwrap();
+    ///
+    /// assert_eq!(lookup[0].index, 16);
+    /// assert_eq!(lookup[0].rolls, [1, 1, 1, 3, 5]);
+    /// assert_eq!(lookup[1].word, "abide");
+    /// assert_eq!(lookup[1].special_char.unwrap().character, '#');
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error on the first word that cannot be looked
+    /// up.
+    pub fn lookup(&self, passphrase: &str) -> Result<Vec<WordLookup>> {
+        passphrase
+            .split_whitespace()
+            .map(|word| self.lookup_word(word))
+            .collect()
+    }
+
+    /// Returns the checksum word for a sequence of word indices.
+    ///
+    /// The index of the checksum word is computed from the first four bytes of
+    /// the SHA-256 hash of the indices, each one written as a 16-bit big-endian
+    /// integer.
+    pub fn checksum_word(&self, indices: &[usize]) -> &str {
+        &self.words[checksum_index(indices)]
+    }
+
+    /// Verifies the checksum word of a passphrase.
+    ///
+    /// The last word of the passphrase must be the checksum word of the other
+    /// ones. The passphrase can contain a special character: as it can be
+    /// ambiguous or even turn a word into another one from the list, every
+    /// possible word is tried.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use diceware::{Config, EmbeddedList, WordList};
+    ///
+    /// let config =
+    ///     Config::with_embedded(EmbeddedList::EN, 6, true).checksum_word(true);
+    /// let passphrase = diceware::make_passphrase(config).unwrap();
+    ///
+    /// let word_list = WordList::embedded(EmbeddedList::EN).unwrap();
+    /// assert!(word_list.verify_checksum(&passphrase).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if a word cannot be found in the list,
+    /// even after removing a special character.
+    pub fn verify_checksum(&self, passphrase: &str) -> Result<bool> {
+        let words: Vec<&str> = passphrase.split_whitespace().collect();
+        if words.len() < 2 {
+            return Ok(false);
+        }
+
+        let direct: Vec<Option<usize>> =
+            words.iter().map(|word| self.index_of(word)).collect();
+
+        // A passphrase contains at most one special character, so at most one
+        // word can be absent from the list.
+        if direct.iter().filter(|index| index.is_none()).count() > 1 {
+            return Ok(false);
+        }
+
+        let matches = |indices: &[usize]| {
+            let (checksum, indices) = indices.split_last()?;
+            Some(checksum_index(indices) == *checksum)
+        };
+
+        if let Some(indices) =
+            direct.iter().copied().collect::<Option<Vec<_>>>()
+        {
+            if matches(&indices) == Some(true) {
+                return Ok(true);
+            }
+        }
+
+        // The special character may have turned a word into another word from
+        // the list, or be removable in several ways: try every candidate.
+        for (i, word) in words.iter().enumerate() {
+            let candidates = self.special_char_candidates(word);
+
+            if candidates.is_empty() && direct[i].is_none() {
+                return Err(LookupError::UnknownWord((*word).to_owned()).into());
+            }
+
+            for (index, _) in candidates {
+                let indices: Option<Vec<usize>> = direct
+                    .iter()
+                    .enumerate()
+                    .map(|(j, &other)| if i == j { Some(index) } else { other })
+                    .collect();
+
+                if indices.as_deref().and_then(matches) == Some(true) {
+                    return Ok(true);
+                }
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Returns the words from the list that can be obtained by removing one
+    /// special character from a word, with the removed special character.
+    ///
+    /// Removing different special characters can give the same word: only the
+    /// first one is kept.
+    fn special_char_candidates(&self, word: &str) -> Vec<(usize, SpecialChar)> {
+        let mut candidates: Vec<(usize, SpecialChar)> = vec![];
+
+        for (position, character) in word.char_indices() {
+            if !SPECIAL_CHARS.contains(character) {
+                continue;
+            }
+
+            let mut candidate = word.to_owned();
+            candidate.remove(position);
+
+            if let Some(index) = self.index_of(&candidate) {
+                if candidates.iter().all(|&(other, _)| other != index) {
+                    let special_char = SpecialChar {
+                        character,
+                        position,
+                    };
+                    candidates.push((index, special_char));
+                }
+            }
+        }
+
+        candidates
+    }
+
+    /// Builds a word lookup result.
+    fn word_lookup(
+        &self,
+        index: usize,
+        special_char: Option<SpecialChar>,
+    ) -> WordLookup {
+        WordLookup {
+            word: self.words[index].clone(),
+            index,
+            rolls: dice_rolls(index),
+            special_char,
+        }
+    }
+}
+
+/// Checks a word list file, returning every problem found.
+///
+/// This performs the same checks as
+/// [`WordList::from_file`](./struct.WordList.html#method.from_file), plus
+/// some lints reporting words that are valid but can cause problems to users,
+/// like non-ASCII or invisible characters. Lints have a
+/// [`Severity::Warning`](./enum.Severity.html) severity. Words changed by the
+/// load options, like words transliterated to ASCII, are reported with a
+/// [`Severity::Note`](./enum.Severity.html) severity.
+///
+/// # Example
+///
+/// ```rust
+/// use diceware::Severity;
+///
+/// if let Ok(diagnostics) = diceware::check_file("words.txt") {
+///     let valid = diagnostics
+///         .iter()
+///         .all(|diagnostic| diagnostic.kind.severity() != Severity::Error);
+/// }
+/// ```
+///
+/// # Errors
+///
+/// This function returns an error only if the file cannot be read.
+#[cfg(feature = "std")]
+pub fn check_file(filename: impl AsRef<Path>) -> Result<Vec<Diagnostic>> {
+    check_file_with_options(filename, &LoadOptions::default())
+}
+
+/// Checks a word list file with custom load options, returning every problem
+/// found.
+///
+/// See [`check_file`](./fn.check_file.html).
+///
+/// # Errors
+///
+/// This function returns an error only if the file cannot be read.
+#[cfg(feature = "std")]
+pub fn check_file_with_options(
+    filename: impl AsRef<Path>,
+    options: &LoadOptions,
+) -> Result<Vec<Diagnostic>> {
+    let path = filename.as_ref();
+
+    File::open(path)
+        .map_err(Error::from)
+        .and_then(|file| check_reader(file, options))
+        .map_err(|err| with_path(err, path))
+}
+
+/// Checks a word list from a reader, like the standard input, returning every
+/// problem found.
+///
+/// See [`check_file`](./fn.check_file.html).
+///
+/// # Errors
+///
+/// This function returns an error only if the content cannot be read.
+#[cfg(feature = "std")]
+pub fn check_reader(
+    reader: impl Read,
+    options: &LoadOptions,
+) -> Result<Vec<Diagnostic>> {
+    let (words, lines, diagnostics) = read_stream(reader, options)?;
+    Ok(check_lines(words, &lines, diagnostics, options))
+}
+
+/// Checks the content of a word list file, returning every problem found.
+///
+/// The content cannot be compressed. See [`check_file`](./fn.check_file.html)
+/// and [`WordList::from_bytes`](./struct.WordList.html#method.from_bytes).
+pub fn check_bytes(content: &[u8], options: &LoadOptions) -> Vec<Diagnostic> {
+    let encoding = options.text_encoding();
+    let content = load::decode(skip_bom(content, encoding), encoding);
+    let (words, lines, diagnostics) = read_lines(&content, options);
+    check_lines(words, &lines, diagnostics, options)
+}
+
+/// Checks the words read from a word list file, returning every problem found.
+fn check_lines(
+    words: Vec<String>,
+    lines: &[usize],
+    mut diagnostics: Vec<Diagnostic>,
+    options: &LoadOptions,
+) -> Vec<Diagnostic> {
+    diagnostics.extend(lint_normalization(&words, lines, options));
+
+    let words = normalize(words, options);
+    check_blanks(&words, lines, &mut diagnostics);
+    check_folding(&words, lines, options, &mut diagnostics);
+    check_words(&words, lines, &mut diagnostics);
+    diagnostics.extend(lint(&words, lines));
+    sort_diagnostics(&mut diagnostics);
+
+    diagnostics
+}
+
+/// Adds a path to IO and word list errors.
+#[cfg(feature = "std")]
+fn with_path(err: Error, path: &Path) -> Error {
+    match err {
+        Error::IO { error, .. } => Error::IO {
+            path: Some(path.to_string_lossy().into_owned()),
+            error,
+        },
+
+        Error::WordList { error, .. } => Error::WordList {
+            path: Some(path.to_string_lossy().into_owned()),
+            error,
+        },
+
+        err => err,
+    }
+}
+
+/// Converts a word list index to its five dice rolls.
+///
+/// # Example
+///
+/// ```rust
+/// assert_eq!(diceware::dice_rolls(0), [1, 1, 1, 1, 1]);
+/// assert_eq!(diceware::dice_rolls(7775), [6, 6, 6, 6, 6]);
+/// ```
+///
+/// # Panics
+///
+/// This function panics if the index is not less than 7776.
+pub fn dice_rolls(index: usize) -> [u8; 5] {
+    assert!(index < WORD_LIST_LENGTH, "index out of range: {index}");
+
+    let mut rolls = [0; 5];
+    let mut rest = index;
+
+    for roll in rolls.iter_mut().rev() {
+        // NOTE(cast): rest % 6 is always less than 6.
+        *roll = (rest % 6) as u8 + 1;
+        rest /= 6;
+    }
+
+    rolls
+}
+
+/// Reads the lines of a word list file.
+///
+/// This returns the words, their line numbers and the problems found on each
+/// line.
+fn read_lines(
+    content: &[u8],
+    options: &LoadOptions,
+) -> (Vec<String>, Vec<usize>, Vec<Diagnostic>) {
+    let mut reader = LineReader::new(options);
+
+    // Like `str::lines`, ignore the final line ending.
+    for line in content.split_inclusive(|&byte| byte == b'\n') {
+        reader.push(line);
+    }
+
+    reader.finish()
+}
+
+/// Skips the UTF-8 byte order mark at the start of a content in a single-byte
+/// encoding, which decoding would turn into `ï»¿`.
+///
+/// In UTF-8, the byte order mark is skipped by [`LineReader`], which keeps
+/// counting its bytes in the offsets of invalid sequences.
+fn skip_bom(content: &[u8], encoding: Encoding) -> &[u8] {
+    match encoding {
+        Encoding::Latin1 | Encoding::Windows1252 => {
+            content.strip_prefix(BOM).unwrap_or(content)
+        }
+        Encoding::Utf8 | Encoding::Auto => content,
+    }
+}
+
+/// Reads the lines of a word list file from a reader, decompressing it if
+/// needed.
+///
+/// Lines are read one by one, unless the encoding is
+/// [`Encoding::Auto`](./enum.Encoding.html), which needs the whole content to
+/// be detected.
+#[cfg(feature = "std")]
+fn read_stream(
+    reader: impl Read,
+    options: &LoadOptions,
+) -> io::Result<(Vec<String>, Vec<usize>, Vec<Diagnostic>)> {
+    let mut reader = compression::open(reader)?;
+    let encoding = options.text_encoding();
+
+    if encoding == Encoding::Auto {
+        let mut content = vec![];
+        reader.read_to_end(&mut content)?;
+        let content = load::decode(&content, encoding);
+        return Ok(read_lines(&content, options));
+    }
+
+    let mut lines = LineReader::new(options);
+    let mut line = vec![];
+    let mut first = true;
+
+    while reader.read_until(b'\n', &mut line)? > 0 {
+        let content = if first {
+            skip_bom(&line, encoding)
+        } else {
+            &line
+        };
+        lines.push(&load::decode(content, encoding));
+        first = false;
+        line.clear();
+    }
+
+    Ok(lines.finish())
+}
+
+/// A reader of the lines of a word list file, fed one line at a time.
+struct LineReader<'a> {
+    options: &'a LoadOptions,
+    words: Vec<String>,
+    lines: Vec<usize>,
+    diagnostics: Vec<Diagnostic>,
+    numbered: Option<bool>,
+    line_number: usize,
+    offset: usize,
+}
+
+impl<'a> LineReader<'a> {
+    /// Creates a line reader.
+    const fn new(options: &'a LoadOptions) -> Self {
+        Self {
+            options,
+            words: vec![],
+            lines: vec![],
+            diagnostics: vec![],
+            numbered: None,
+            line_number: 0,
+            offset: 0,
+        }
+    }
+
+    /// Reads a line, with its line ending if any.
+    fn push(&mut self, line: &[u8]) {
+        self.line_number += 1;
+        let line_number = self.line_number;
+        let mut line_offset = self.offset;
+        self.offset += line.len();
+
+        let mut line = line.strip_suffix(b"\n").unwrap_or(line);
+        line = line.strip_suffix(b"\r").unwrap_or(line);
+
+        if line_number == 1 {
+            if let Some(rest) = line.strip_prefix(BOM) {
+                line = rest;
+                line_offset += BOM.len();
+            }
+        }
+
+        let position = self.words.len();
+
+        let line = match core::str::from_utf8(line) {
+            Ok(line) => line,
+            Err(error) => {
+                self.diagnostics.push(Diagnostic {
+                    line: Some(line_number),
+                    kind: DiagnosticKind::InvalidUtf8 {
+                        offset: line_offset + error.valid_up_to(),
+                    },
+                });
+
+                self.words.push(String::from_utf8_lossy(line).into_owned());
+                self.lines.push(line_number);
+                return;
+            }
+        };
+
+        if !self.options.is_strict() && (is_blank(line) || is_comment(line)) {
+            return;
+        }
+
+        let (rolls, word) = split_numbering(line);
+        let numbered = *self.numbered.get_or_insert(rolls.is_some());
+
+        if numbered && position < WORD_LIST_LENGTH {
+            let expected = dice_rolls_string(position);
+            match rolls {
+                Some(found) if found != expected => {
+                    self.diagnostics.push(Diagnostic {
+                        line: Some(line_number),
+                        kind: DiagnosticKind::InvalidNumbering {
+                            expected,
+                            found: found.to_owned(),
+                        },
+                    });
+                }
+                Some(_) => (),
+                None => self.diagnostics.push(Diagnostic {
+                    line: Some(line_number),
+                    kind: DiagnosticKind::MissingNumbering { expected },
+                }),
+            }
+        }
+
+        self.words.push(word.to_owned());
+        self.lines.push(line_number);
+    }
+
+    /// Returns the words, their line numbers and the problems found.
+    fn finish(self) -> (Vec<String>, Vec<usize>, Vec<Diagnostic>) {
+        (self.words, self.lines, self.diagnostics)
+    }
+}
+
+/// Looks for empty words and words containing whitespace.
+fn check_blanks(
+    words: &[String],
+    lines: &[usize],
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for (word, &line) in words.iter().zip(lines) {
+        if word.is_empty() {
+            diagnostics.push(Diagnostic {
+                line: Some(line),
+                kind: DiagnosticKind::EmptyLine,
+            });
+        } else if word.contains(char::is_whitespace) {
+            diagnostics.push(Diagnostic {
+                line: Some(line),
+                kind: DiagnosticKind::Whitespace(word.clone()),
+            });
+        }
+    }
+}
+
+/// Looks for duplicates and checks the length of the list, returning the
+/// index of the words.
+fn check_words(
+    words: &[String],
+    lines: &[usize],
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Index {
+    let mut index = Index::new();
+    for (i, word) in words.iter().enumerate() {
+        match index.get(word) {
+            None => {
+                index.insert(word.clone(), i);
+            }
+
+            Some(&first) => diagnostics.push(Diagnostic {
+                line: Some(lines[i]),
+                kind: DiagnosticKind::DuplicateWord {
+                    word: word.clone(),
+                    first_line: lines[first],
+                },
+            }),
+        }
+    }
+
+    if words.len() != WORD_LIST_LENGTH {
+        diagnostics.push(Diagnostic {
+            line: None,
+            kind: DiagnosticKind::InvalidLength(words.len()),
+        });
+    }
+
+    index
+}
+
+/// Looks for words that are valid but can cause problems to users.
+fn lint(words: &[String], lines: &[usize]) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+
+    for (word, &line) in words.iter().zip(lines) {
+        let mut lint = |kind| {
+            diagnostics.push(Diagnostic {
+                line: Some(line),
+                kind,
+            });
+        };
+
+        if word.chars().any(is_invisible) {
+            lint(DiagnosticKind::InvisibleChar(word.clone()));
+        }
+
+        if !word.is_ascii() {
+            lint(DiagnosticKind::NonAscii(word.clone()));
+        }
+
+        if word.chars().any(char::is_uppercase) {
+            lint(DiagnosticKind::Uppercase(word.clone()));
+        }
+
+        if !word.as_str().is_single_script() {
+            lint(DiagnosticKind::MixedScript(word.clone()));
+        }
+    }
+
+    diagnostics.extend(lint_confusables(words, lines));
+    diagnostics
+}
+
+/// Looks for words that are not in the normalization form of the list, and
+/// reports the words transliterated to ASCII.
+fn lint_normalization(
+    words: &[String],
+    lines: &[usize],
+    options: &LoadOptions,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let form = options.normalization_form();
+
+    for (word, &line) in words.iter().zip(lines) {
+        let normalized = form.normalize(word);
+        if normalized != word.as_str() {
+            diagnostics.push(Diagnostic {
+                line: Some(line),
+                kind: DiagnosticKind::NotNormalized {
+                    word: word.clone(),
+                    form,
+                },
+            });
+        }
+
+        let folded = options.normalize(word);
+        if folded != normalized && folded.is_ascii() {
+            diagnostics.push(Diagnostic {
+                line: Some(line),
+                kind: DiagnosticKind::Folded {
+                    word: word.clone(),
+                    folded: folded.into_owned(),
+                },
+            });
+        }
+    }
+
+    diagnostics
+}
+
+/// Looks for words that have not been transliterated to ASCII, if requested.
+fn check_folding(
+    words: &[String],
+    lines: &[usize],
+    options: &LoadOptions,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    if !options.folds_to_ascii() {
+        return;
+    }
+
+    for (word, &line) in words.iter().zip(lines) {
+        if !word.is_ascii() {
+            diagnostics.push(Diagnostic {
+                line: Some(line),
+                kind: DiagnosticKind::NotFoldable(word.clone()),
+            });
+        }
+    }
+}
+
+/// Looks for words that look like another word from the list, i.e. that have
+/// the same Unicode confusable skeleton.
+fn lint_confusables(words: &[String], lines: &[usize]) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let mut skeletons: BTreeMap<String, usize> = BTreeMap::new();
+
+    for (i, word) in words.iter().enumerate() {
+        match skeletons.entry(skeleton(word).collect()) {
+            Entry::Vacant(entry) => {
+                entry.insert(i);
+            }
+
+            Entry::Occupied(entry) => {
+                let other = *entry.get();
+
+                // Duplicates are already reported as errors.
+                if words[other] != *word {
+                    diagnostics.push(Diagnostic {
+                        line: Some(lines[i]),
+                        kind: DiagnosticKind::Confusable {
+                            word: word.clone(),
+                            other: words[other].clone(),
+                            other_line: lines[other],
+                        },
+                    });
+                }
+            }
+        }
+    }
+
+    diagnostics
+}
+
+/// Normalizes the words of a list.
+fn normalize(words: Vec<String>, options: &LoadOptions) -> Vec<String> {
+    words
+        .into_iter()
+        .map(|word| match options.normalize(&word) {
+            Cow::Borrowed(_) => word,
+            Cow::Owned(normalized) => normalized,
+        })
+        .collect()
+}
+
+/// Returns whether a line is blank.
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Returns whether a line is a comment, i.e. starts with `#` followed by a
+/// space or a tab.
+///
+/// A single `#` is not a comment, as it is a word in some lists.
+fn is_comment(line: &str) -> bool {
+    line.starts_with("# ") || line.starts_with("#\t")
+}
+
+/// Returns whether a character is invisible, i.e. a control character or a
+/// zero-width format character.
+fn is_invisible(c: char) -> bool {
+    c.is_control()
+        || matches!(c, '\u{200b}'..='\u{200f}' | '\u{2060}'..='\u{2064}')
+        || c == '\u{feff}'
+}
+
+/// Sorts diagnostics by line, keeping problems concerning the whole list last.
+fn sort_diagnostics(diagnostics: &mut [Diagnostic]) {
+    diagnostics
+        .sort_by_key(|diagnostic| (diagnostic.line.is_none(), diagnostic.line));
+}
+
+/// Converts a word list index to its five dice rolls, as a string.
+fn dice_rolls_string(index: usize) -> String {
+    dice_rolls(index)
+        .iter()
+        .map(|roll| char::from(b'0' + roll))
+        .collect()
+}
+
+/// Splits the dice rolls numbering a line, if any, from the word.
+fn split_numbering(line: &str) -> (Option<&str>, &str) {
+    let bytes = line.as_bytes();
+
+    let numbered = bytes.len() > 5
+        && bytes[..5].iter().all(|byte| (b'1'..=b'6').contains(byte))
+        && (bytes[5] == b' ' || bytes[5] == b'\t');
+
+    if numbered {
+        (Some(&line[..5]), line[5..].trim_start_matches([' ', '\t']))
+    } else {
+        (None, line)
+    }
+}
+
+/// Computes the index of the checksum word for a sequence of word indices.
+fn checksum_index(indices: &[usize]) -> usize {
+    let mut hasher = Sha256::new();
+    for &index in indices {
+        // NOTE(cast): indices are less than 7776.
+        hasher.update((index as u16).to_be_bytes());
+    }
+    let hash = hasher.finalize();
+
+    let value = u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]]);
+    value as usize % WORD_LIST_LENGTH
+}
+
+/// Gets the corresponding embedded word list.
+pub fn embedded_list(list: &EmbeddedList) -> &[&str; 7776] {
+    match list {
+        EmbeddedList::EN => &embedded::EN,
+        EmbeddedList::FR => &embedded::FR,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::fs;
+
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{
+        insecure,
+        test_util::{arb_list, valid_content},
+        Config, EmbeddedList, Encoding, Normalization, Severity,
+    };
+
+    /// Encodes a string in Latin-1.
+    fn encode_latin1(content: &str) -> Vec<u8> {
+        content.chars().map(|c| u8::try_from(c).unwrap()).collect()
+    }
+
+    /// Parses a word list file content, returning the diagnostics.
+    fn diagnostics(content: &str) -> Vec<Diagnostic> {
+        diagnostics_with_options(content, &LoadOptions::default())
+    }
+
+    /// Parses a word list file content with custom load options, returning
+    /// the diagnostics.
+    fn diagnostics_with_options(
+        content: &str,
+        options: &LoadOptions,
+    ) -> Vec<Diagnostic> {
+        diagnostics_from_bytes(content.as_bytes(), options)
+    }
+
+    /// Parses a raw word list file content with custom load options,
+    /// returning the diagnostics.
+    fn diagnostics_from_bytes(
+        content: &[u8],
+        options: &LoadOptions,
+    ) -> Vec<Diagnostic> {
+        match WordList::from_bytes(content, options) {
+            Err(Error::WordList {
+                error: WordListError::Invalid(diagnostics),
+                ..
+            }) => diagnostics,
+            Err(err) => panic!("unexpected error: {err}"),
+            Ok(_) => panic!("unexpected valid list"),
+        }
+    }
+
+    #[test]
+    fn parses_a_valid_list() {
+        let word_list = WordList::from_bytes(
+            valid_content().as_bytes(),
+            &LoadOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(word_list.words()[42], "word42");
+    }
+
+    #[test]
+    fn parses_a_list_with_crlf_line_endings() {
+        let content = valid_content().replace('\n', "\r\n");
+        let word_list =
+            WordList::from_bytes(content.as_bytes(), &LoadOptions::default())
+                .unwrap();
+
+        assert_eq!(word_list.words()[42], "word42");
+    }
+
+    #[test]
+    fn parses_a_numbered_list() {
+        let content: String = (0..WORD_LIST_LENGTH)
+            .map(|i| format!("{}\tword{i}\n", dice_rolls_string(i)))
+            .collect();
+        let word_list =
+            WordList::from_bytes(content.as_bytes(), &LoadOptions::default())
+                .unwrap();
+
+        assert_eq!(word_list.words()[42], "word42");
+    }
+
+    #[test]
+    fn fingerprints_the_words_regardless_of_the_format() {
+        let options = LoadOptions::default();
+        let plain =
+            WordList::from_bytes(valid_content().as_bytes(), &options).unwrap();
+
+        let numbered: String = (0..WORD_LIST_LENGTH)
+            .map(|i| format!("{}\tword{i}\n", dice_rolls_string(i)))
+            .collect();
+        let numbered =
+            WordList::from_bytes(numbered.as_bytes(), &options).unwrap();
+
+        let mut words = plain.words().to_vec();
+        words.swap(0, 1);
+        let swapped = WordList::new(words).unwrap();
+
+        assert_eq!(plain.fingerprint(), numbered.fingerprint());
+        assert_ne!(plain.fingerprint(), swapped.fingerprint());
+    }
+
+    #[test]
+    fn reports_every_problem_with_its_line() {
+        let mut content = valid_content();
+        content.push_str("word42\n\nwith space\nword3\n");
+        let options = LoadOptions::default().strict(true);
+
+        assert_eq!(
+            diagnostics_with_options(&content, &options),
+            vec![
+                Diagnostic {
+                    line: Some(7777),
+                    kind: DiagnosticKind::DuplicateWord {
+                        word: "word42".to_owned(),
+                        first_line: 43,
+                    },
+                },
+                Diagnostic {
+                    line: Some(7778),
+                    kind: DiagnosticKind::EmptyLine,
+                },
+                Diagnostic {
+                    line: Some(7779),
+                    kind: DiagnosticKind::Whitespace("with space".to_owned()),
+                },
+                Diagnostic {
+                    line: Some(7780),
+                    kind: DiagnosticKind::DuplicateWord {
+                        word: "word3".to_owned(),
+                        first_line: 4,
+                    },
+                },
+                Diagnostic {
+                    line: None,
+                    kind: DiagnosticKind::InvalidLength(7780),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_each_word_in_every_constructor() {
+        let mut words: Vec<String> =
+            (0..WORD_LIST_LENGTH).map(|i| format!("word{i}")).collect();
+        words[3] = String::new();
+        words[5] = "with space".to_owned();
+        let diagnostics = match WordList::new(words) {
+            Err(Error::WordList {
+                error: WordListError::Invalid(diagnostics),
+                ..
+            }) => diagnostics,
+            Err(err) => panic!("unexpected error: {err}"),
+            Ok(_) => panic!("unexpected valid list"),
+        };
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic {
+                    line: Some(4),
+                    kind: DiagnosticKind::EmptyLine,
+                },
+                Diagnostic {
+                    line: Some(6),
+                    kind: DiagnosticKind::Whitespace("with space".to_owned()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_the_embedded_lists_back_from_a_file() {
+        for list in [EmbeddedList::EN, EmbeddedList::FR] {
+            let word_list = WordList::embedded(list).unwrap();
+            let content = word_list.words().join("\n");
+            let from_bytes = WordList::from_bytes(
+                content.as_bytes(),
+                &LoadOptions::default(),
+            )
+            .unwrap();
+
+            assert_eq!(from_bytes.words(), word_list.words());
+        }
+    }
+
+    #[test]
+    fn ignores_the_bom_comments_and_blank_lines() {
+        let content = format!(
+            "\u{feff}# A word list\r\n\r\n#\tversion 1\r\n{}\r\n  \r\n",
+            valid_content().trim_end().replace('\n', "\r\n")
+        );
+        let options = LoadOptions::default();
+        let word_list =
+            WordList::from_bytes(content.as_bytes(), &options).unwrap();
+
+        assert_eq!(word_list.words()[0], "word0");
+        assert_eq!(word_list.words()[7775], "word7775");
+
+        // Line numbers count comments and blank lines.
+        let content = content.replacen("word1\r\n", "word0\r\n", 1);
+        assert_eq!(
+            diagnostics(&content),
+            vec![Diagnostic {
+                line: Some(5),
+                kind: DiagnosticKind::DuplicateWord {
+                    word: "word0".to_owned(),
+                    first_line: 4,
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn keeps_a_single_hash_as_a_word() {
+        let content = valid_content().replacen("word1\n", "#\n", 1);
+        let options = LoadOptions::default();
+        let word_list =
+            WordList::from_bytes(content.as_bytes(), &options).unwrap();
+
+        assert_eq!(word_list.index_of("#"), Some(1));
+    }
+
+    #[test]
+    fn reads_comments_as_words_in_strict_mode() {
+        let content = format!("\u{feff}# A word list\n{}", valid_content());
+        let options = LoadOptions::default().strict(true);
+
+        assert_eq!(
+            diagnostics_with_options(&content, &options),
+            vec![
+                Diagnostic {
+                    line: Some(1),
+                    kind: DiagnosticKind::Whitespace(
+                        "# A word list".to_owned()
+                    ),
+                },
+                Diagnostic {
+                    line: None,
+                    kind: DiagnosticKind::InvalidLength(7777),
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn reads_a_compressed_file() {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(
+            vec![],
+            flate2::Compression::default(),
+        );
+        encoder.write_all(valid_content().as_bytes()).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("compressed.txt.gz");
+        fs::write(&path, encoder.finish().unwrap()).unwrap();
+        let word_list = WordList::from_file(&path);
+
+        assert_eq!(word_list.unwrap().words()[42], "word42");
+    }
+
+    #[test]
+    fn adds_the_path_to_io_errors() {
+        let path = Path::new("/nonexistent/words.txt");
+        let err = WordList::from_file(path).unwrap_err();
+
+        assert!(
+            matches!(&err, Error::IO { path: Some(p), .. } if p == "/nonexistent/words.txt")
+        );
+        assert!(err.to_string().starts_with("/nonexistent/words.txt: "));
+        assert!(std::error::Error::source(&err).is_none());
+    }
+
+    #[test]
+    fn reads_a_list_from_a_reader() {
+        let content = valid_content();
+        let options = LoadOptions::default();
+        let word_list = WordList::from_reader(content.as_bytes(), &options);
+
+        assert_eq!(word_list.unwrap().words()[42], "word42");
+
+        let err =
+            WordList::from_reader(&b"a\nb\na\n"[..], &options).unwrap_err();
+        assert!(matches!(err, Error::WordList { path: None, .. }));
+    }
+
+    #[test]
+    fn adds_the_path_to_word_list_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("invalid-list.txt");
+        fs::write(&path, "a\nb\na\n").unwrap();
+        let err = WordList::from_file(&path).unwrap_err();
+
+        match &err {
+            Error::WordList {
+                path: Some(p),
+                error: WordListError::Invalid(diagnostics),
+            } => {
+                assert_eq!(Path::new(p), path);
+                assert_eq!(diagnostics.len(), 2);
+                assert_eq!(diagnostics[0].line, Some(3));
+            }
+            _ => panic!("unexpected error: {err}"),
+        }
+
+        assert!(std::error::Error::source(&err).is_none());
+    }
+
+    #[test]
+    fn checks_a_file_with_lints() {
+        let mut content = valid_content();
+        content = content.replacen("word1\n", "Word1\n", 1);
+        content = content.replacen("word2\n", "wörd2\n", 1);
+        content = content.replacen("word3\n", "word\u{200b}3\n", 1);
+        content.push_str("word4\n");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lints.txt");
+        fs::write(&path, content).unwrap();
+        let diagnostics = check_file(&path).unwrap();
+
+        let kinds: Vec<(Option<usize>, Severity)> = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.kind.severity()))
+            .collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                (Some(2), Severity::Warning),
+                (Some(3), Severity::Warning),
+                (Some(4), Severity::Warning),
+                (Some(4), Severity::Warning),
+                (Some(7777), Severity::Error),
+                (None, Severity::Error),
+            ]
+        );
+        assert_eq!(
+            diagnostics[0].kind,
+            DiagnosticKind::Uppercase("Word1".to_owned())
+        );
+        assert_eq!(
+            diagnostics[2].kind,
+            DiagnosticKind::InvisibleChar("word\u{200b}3".to_owned())
+        );
+    }
+
+    #[test]
+    fn detects_duplicates_after_normalization() {
+        let mut content = valid_content();
+        content = content.replacen("word1\n", "\u{e9}t\u{e9}\n", 1);
+        content = content.replacen("word2\n", "e\u{301}te\u{301}\n", 1);
+
+        assert_eq!(
+            diagnostics(&content),
+            vec![Diagnostic {
+                line: Some(3),
+                kind: DiagnosticKind::DuplicateWord {
+                    word: "\u{e9}t\u{e9}".to_owned(),
+                    first_line: 2,
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn normalizes_a_list_with_nfkc() {
+        let mut content = valid_content();
+        content = content.replacen("word1\n", "\u{fb01}n\n", 1);
+        content = content.replacen("word2\n", "fin\n", 1);
+
+        assert!(WordList::from_bytes(
+            content.as_bytes(),
+            &LoadOptions::default()
+        )
+        .is_ok());
+
+        let options = LoadOptions::default().normalization(Normalization::NFKC);
+        let result = WordList::from_bytes(content.as_bytes(), &options);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Word list: line 3: duplicate word \"fin\", first seen on line 2"
+        );
+    }
+
+    #[test]
+    fn looks_up_a_word_in_another_normalization_form() {
+        let mut content = valid_content();
+        content = content.replacen("word1\n", "\u{e9}t\u{e9}\n", 1);
+        let word_list =
+            WordList::from_bytes(content.as_bytes(), &LoadOptions::default())
+                .unwrap();
+
+        let lookup = word_list.lookup_word("e\u{301}te\u{301}").unwrap();
+        assert_eq!(lookup.index, 1);
+        assert_eq!(lookup.word, "\u{e9}t\u{e9}");
+    }
+
+    #[test]
+    fn checks_a_file_with_unicode_lints() {
+        let mut content = valid_content();
+        content = content.replacen("word1\n", "e\u{301}\n", 1);
+        content = content.replacen("word2\n", "p\u{430}ss\n", 1);
+        content = content.replacen("word3\n", "rn\n", 1);
+        content = content.replacen("word4\n", "m\n", 1);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("unicode-lints.txt");
+        fs::write(&path, content).unwrap();
+        let diagnostics = check_file(&path).unwrap();
+
+        let kinds: Vec<&DiagnosticKind> = diagnostics
+            .iter()
+            .map(|diagnostic| &diagnostic.kind)
+            .collect();
+
+        assert!(kinds.contains(&&DiagnosticKind::NotNormalized {
+            word: "e\u{301}".to_owned(),
+            form: Normalization::NFC,
+        }));
+        assert!(kinds
+            .contains(&&DiagnosticKind::MixedScript("p\u{430}ss".to_owned())));
+        assert!(kinds.contains(&&DiagnosticKind::Confusable {
+            word: "m".to_owned(),
+            other: "rn".to_owned(),
+            other_line: 4,
+        }));
+        assert!(diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.kind.severity() == Severity::Warning));
+    }
+
+    #[test]
+    fn folds_a_list_to_ascii() {
+        let mut content = valid_content();
+        content = content.replacen("word1\n", "\u{e9}glise\n", 1);
+        content = content.replacen("word2\n", "stra\u{df}e\n", 1);
+        let options = LoadOptions::default().ascii_folding(true);
+        let word_list =
+            WordList::from_bytes(content.as_bytes(), &options).unwrap();
+
+        assert_eq!(word_list.words()[1], "eglise");
+        assert_eq!(word_list.words()[2], "strasse");
+        assert_eq!(word_list.index_of("\u{e9}glise"), Some(1));
+        assert_eq!(word_list.index_of("eglise"), Some(1));
+    }
+
+    #[test]
+    fn rejects_duplicates_after_folding() {
+        let mut content = valid_content();
+        content = content.replacen("word1\n", "\u{e9}t\u{e9}\n", 1);
+        content = content.replacen("word2\n", "ete\n", 1);
+        content = content.replacen("word3\n", "\u{65e5}\n", 1);
+        let options = LoadOptions::default().ascii_folding(true);
+
+        let diagnostics =
+            match WordList::from_bytes(content.as_bytes(), &options) {
+                Err(Error::WordList {
+                    error: WordListError::Invalid(diagnostics),
+                    ..
+                }) => diagnostics,
+                _ => panic!("unexpected result"),
+            };
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic {
+                    line: Some(3),
+                    kind: DiagnosticKind::DuplicateWord {
+                        word: "ete".to_owned(),
+                        first_line: 2,
+                    },
+                },
+                Diagnostic {
+                    line: Some(4),
+                    kind: DiagnosticKind::NotFoldable("\u{65e5}".to_owned()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_folded_words() {
+        let mut content = valid_content();
+        content = content.replacen("word1\n", "\u{e9}glise\n", 1);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("folded.txt");
+        fs::write(&path, content).unwrap();
+        let options = LoadOptions::default().ascii_folding(true);
+        let diagnostics = check_file_with_options(&path, &options).unwrap();
+
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic {
+                line: Some(2),
+                kind: DiagnosticKind::Folded {
+                    word: "\u{e9}glise".to_owned(),
+                    folded: "eglise".to_owned(),
+                },
+            }]
+        );
+        assert_eq!(diagnostics[0].kind.severity(), Severity::Note);
+    }
+
+    #[test]
+    fn reports_invalid_utf8() {
+        let mut content = valid_content().into_bytes();
+        content[0] = 0xff;
+
+        match WordList::from_bytes(&content, &LoadOptions::default()) {
+            Err(Error::WordList {
+                error: WordListError::Invalid(diagnostics),
+                ..
+            }) => {
+                assert_eq!(
+                    diagnostics,
+                    vec![Diagnostic {
+                        line: Some(1),
+                        kind: DiagnosticKind::InvalidUtf8 { offset: 0 },
+                    }]
+                );
+            }
+            _ => panic!("invalid UTF-8 not reported"),
+        }
+    }
+
+    #[test]
+    fn reports_the_offset_of_invalid_utf8() {
+        let content = valid_content().replacen("word2\n", "\u{e9}t\u{e9}\n", 1);
+        let content = encode_latin1(&content);
+
+        assert_eq!(
+            diagnostics_from_bytes(&content, &LoadOptions::default()),
+            vec![Diagnostic {
+                line: Some(3),
+                kind: DiagnosticKind::InvalidUtf8 { offset: 12 },
+            }]
+        );
+    }
+
+    #[test]
+    fn reads_a_latin1_list() {
+        let content = valid_content().replacen("word2\n", "\u{e9}t\u{e9}\n", 1);
+        let content = encode_latin1(&content);
+
+        for encoding in
+            [Encoding::Latin1, Encoding::Windows1252, Encoding::Auto]
+        {
+            let options = LoadOptions::default().encoding(encoding);
+            let word_list = WordList::from_bytes(&content, &options).unwrap();
+
+            assert_eq!(word_list.words()[2], "\u{e9}t\u{e9}");
+        }
+    }
+
+    #[test]
+    fn skips_the_bom_of_a_latin1_list() {
+        let mut content = BOM.to_vec();
+        content.extend(encode_latin1(&valid_content()));
+
+        for encoding in [Encoding::Latin1, Encoding::Windows1252] {
+            let options = LoadOptions::default().encoding(encoding);
+            let word_list = WordList::from_bytes(&content, &options).unwrap();
+
+            assert_eq!(word_list.words()[0], "word0");
+            assert!(check_reader(&content[..], &options).unwrap().is_empty());
+        }
+    }
+
+    #[test]
+    fn reads_a_stream_like_its_content() {
+        let content = format!(
+            "\u{feff}# comment\r\n\r\n{}not\u{a0}valid",
+            valid_content().replacen("word2\n", "\u{e9}t\u{e9}\n", 1)
+        );
+        let latin1 = encode_latin1(&content.replace('\u{feff}', ""));
+
+        for (content, encoding) in [
+            (content.as_bytes(), Encoding::Utf8),
+            (&latin1[..], Encoding::Latin1),
+            (&latin1[..], Encoding::Auto),
+        ] {
+            for strict in [false, true] {
+                let options =
+                    LoadOptions::default().encoding(encoding).strict(strict);
+
+                assert_eq!(
+                    check_reader(content, &options).unwrap(),
+                    check_bytes(content, &options)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn reads_a_windows_1252_list() {
+        let mut content = valid_content().into_bytes();
+        content.splice(0..5, *b"\x80\x9c\x92s");
+        let options = LoadOptions::default().encoding(Encoding::Windows1252);
+        let word_list = WordList::from_bytes(&content, &options).unwrap();
+
+        assert_eq!(word_list.words()[0], "\u{20ac}\u{153}\u{2019}s");
+    }
+
+    #[test]
+    fn detects_utf8_lists() {
+        let content = valid_content().replacen("word2\n", "\u{e9}t\u{e9}\n", 1);
+        let options = LoadOptions::default().encoding(Encoding::Auto);
+        let word_list =
+            WordList::from_bytes(content.as_bytes(), &options).unwrap();
+
+        assert_eq!(word_list.words()[2], "\u{e9}t\u{e9}");
+    }
+
+    #[test]
+    fn reports_invalid_numbering() {
+        let content: String = (0..WORD_LIST_LENGTH)
+            .map(|i| match i {
+                1 => "11113\tword1\n".to_owned(),
+                2 => "word2\n".to_owned(),
+                _ => format!("{} word{i}\n", dice_rolls_string(i)),
+            })
+            .collect();
+
+        assert_eq!(
+            diagnostics(&content),
+            vec![
+                Diagnostic {
+                    line: Some(2),
+                    kind: DiagnosticKind::InvalidNumbering {
+                        expected: "11112".to_owned(),
+                        found: "11113".to_owned(),
+                    },
+                },
+                Diagnostic {
+                    line: Some(3),
+                    kind: DiagnosticKind::MissingNumbering {
+                        expected: "11113".to_owned(),
+                    },
+                },
+            ]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn looks_up_a_passphrase(
+            ref list in arb_list(),
+            n in 1..20usize,
+            seed: u64
+        ) {
+            let word_list = WordList::embedded(list.clone()).unwrap();
+
+            let config = Config::with_embedded(list.clone(), n, false);
+            let passphrase = insecure::make_passphrase(config, seed).unwrap();
+            let lookup = word_list.lookup(&passphrase).unwrap();
+
+            prop_assert_eq!(lookup.len(), n);
+            for (word, lookup) in passphrase.split(' ').zip(lookup) {
+                prop_assert_eq!(&lookup.word, word);
+                prop_assert_eq!(&word_list.words()[lookup.index], word);
+                prop_assert_eq!(lookup.special_char, None);
+            }
+        }
+    }
+
+    #[test]
+    fn looks_up_a_word_with_a_special_char() {
+        let word_list = WordList::embedded(EmbeddedList::EN).unwrap();
+        let lookup = word_list.lookup_word("abb%ey").unwrap();
+
+        assert_eq!(lookup.word, "abbey");
+        assert_eq!(lookup.index, 16);
+        assert_eq!(lookup.rolls, [1, 1, 1, 3, 5]);
+        assert_eq!(
+            lookup.special_char,
+            Some(SpecialChar {
+                character: '%',
+                position: 3
+            })
+        );
+    }
+
+    #[test]
+    fn returns_an_error_if_a_word_is_unknown() {
+        let word_list = WordList::embedded(EmbeddedList::EN).unwrap();
+        let result = word_list.lookup("abbey notaword");
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Lookup: notaword: unknown word"
+        );
+    }
+
+    #[test]
+    fn returns_an_error_if_a_word_is_ambiguous() {
+        // Removing either `1` or `2` from `a1a2` gives a word from the list.
+        let word_list = WordList::new(
+            (0..WORD_LIST_LENGTH - 2)
+                .map(|i| format!("word{i}"))
+                .chain(["a1a".to_owned(), "aa2".to_owned()])
+                .collect(),
+        )
+        .unwrap();
+        let result = word_list.lookup_word("a1a2");
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Lookup: a1a2: ambiguous word"
+        );
+    }
+
+    #[test]
+    fn converts_indices_to_dice_rolls() {
+        assert_eq!(dice_rolls(0), [1, 1, 1, 1, 1]);
+        assert_eq!(dice_rolls(1), [1, 1, 1, 1, 2]);
+        assert_eq!(dice_rolls(6), [1, 1, 1, 2, 1]);
+        assert_eq!(dice_rolls(7775), [6, 6, 6, 6, 6]);
+    }
+
+    #[test]
+    fn returns_an_error_if_a_list_contains_duplicates() {
+        let mut words: Vec<String> =
+            (0..WORD_LIST_LENGTH).map(|i| format!("word{i}")).collect();
+        words[42] = "word0".to_owned();
+        let result = WordList::new(words);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Word list: line 43: duplicate word \"word0\", first seen on line 1"
+        );
+    }
+}
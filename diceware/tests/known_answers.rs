@@ -0,0 +1,75 @@
+// diceware - A Diceware passphrase generator.
+// Copyright (C) 2018 Jean-Philippe Cugnet <jean-philippe@cugnet.eu>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Known-answer tests for the insecure seeded generator.
+
+#![allow(clippy::unwrap_used)]
+
+use diceware::{insecure, Config, EmbeddedList};
+
+const VECTORS: &str = include_str!("vectors/known_answers.tsv");
+
+#[test]
+fn generates_the_known_answers() {
+    let vectors = VECTORS
+        .lines()
+        .filter(|line| !line.starts_with('#') && !line.is_empty());
+
+    let mut count = 0;
+    for vector in vectors {
+        let fields: Vec<&str> = vector.split('\t').collect();
+        let [list, seed, words, with_special_char, expected] = fields[..]
+        else {
+            panic!("invalid test vector: {vector}");
+        };
+
+        let list = match list {
+            "en" => EmbeddedList::EN,
+            "fr" => EmbeddedList::FR,
+            _ => panic!("unknown list: {list}"),
+        };
+
+        let config = Config::with_embedded(
+            list,
+            words.parse().unwrap(),
+            with_special_char.parse().unwrap(),
+        );
+        let passphrase =
+            insecure::make_passphrase(config, seed.parse().unwrap()).unwrap();
+
+        assert_eq!(passphrase, expected, "test vector: {vector}");
+        count += 1;
+    }
+
+    assert!(count > 0);
+}
+
+#[test]
+fn generates_the_same_passphrases_from_the_same_seed() {
+    let config = Config::with_embedded(EmbeddedList::FR, 8, true);
+    let first: Vec<String> = insecure::passphrases(config, 2022)
+        .unwrap()
+        .take(5)
+        .collect();
+
+    let config = Config::with_embedded(EmbeddedList::FR, 8, true);
+    let second: Vec<String> = insecure::passphrases(config, 2022)
+        .unwrap()
+        .take(5)
+        .collect();
+
+    assert_eq!(first, second);
+}